@@ -0,0 +1,125 @@
+//! The `rules` and `explain` commands, documenting the built-in rules
+
+use anyhow::Result;
+use std::fmt::Write;
+use yaml_lint_core::config::{BUILTIN_RULES, unknown_rule};
+use yaml_lint_core::rules::RuleLevel;
+use yaml_lint_core::{Config, RuleRegistry};
+
+/// Presets shown in the rule list, with their rule registries
+fn presets() -> Result<[(&'static str, RuleRegistry); 2]> {
+    Ok([
+        ("default", Config::with_default_preset().create_registry()?),
+        ("relaxed", Config::with_relaxed_preset().create_registry()?),
+    ])
+}
+
+/// Level of `rule` in a preset's registry, `disable` if the preset leaves
+/// it out
+fn preset_level(registry: &RuleRegistry, rule: &str) -> RuleLevel {
+    registry.get_level(rule).unwrap_or(RuleLevel::Disable)
+}
+
+/// Table of the rules with their level in each preset and fixability
+pub fn rule_list() -> Result<String> {
+    let registry = RuleRegistry::with_defaults();
+    let presets = presets()?;
+    let width = BUILTIN_RULES
+        .iter()
+        .map(|rule| rule.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{:width$}  default  relaxed  fixable\n", "rule");
+    for rule in BUILTIN_RULES {
+        let Some(metadata) = registry.get(rule).map(|rule| rule.metadata()) else {
+            continue;
+        };
+        write!(out, "{:width$}", rule)?;
+        for (_, preset) in &presets {
+            write!(out, "  {:7}", preset_level(preset, rule).to_string())?;
+        }
+        writeln!(out, "  {}", if metadata.fixable { "yes" } else { "no" })?;
+    }
+    out.push_str("\nRun `yaml-lint explain <rule>` for what a rule checks and its options.\n");
+
+    Ok(out)
+}
+
+/// What `rule` checks, why, its options and examples
+pub fn explanation(rule: &str) -> Result<String> {
+    let registry = RuleRegistry::with_defaults();
+    let metadata = registry
+        .get(rule)
+        .map(|rule| rule.metadata())
+        .ok_or_else(|| unknown_rule(rule))?;
+
+    let mut out = format!("{}\n\n{}\n\n", metadata.name, metadata.description);
+    writeln!(out, "Why: {}\n", metadata.rationale)?;
+
+    let levels: Vec<String> = presets()?
+        .iter()
+        .map(|(name, preset)| format!("{} in the {} preset", preset_level(preset, rule), name))
+        .collect();
+    writeln!(out, "Level: {}", levels.join(", "))?;
+    writeln!(
+        out,
+        "Fixable: {}",
+        if metadata.fixable { "yes" } else { "no" }
+    )?;
+
+    if !metadata.options.is_empty() {
+        out.push_str("\nOptions:\n");
+        for option in metadata.options {
+            let default = option.default.unwrap_or("unset");
+            writeln!(
+                out,
+                "  {}: {} (default: {})\n      {}",
+                option.name, option.value_type, default, option.description
+            )?;
+        }
+    }
+
+    for (title, example) in [
+        ("Bad", metadata.bad_example),
+        ("Good", metadata.good_example),
+    ] {
+        writeln!(out, "\n{}:", title)?;
+        for line in example.lines() {
+            writeln!(out, "    {}", line)?;
+        }
+    }
+
+    writeln!(out, "\nDocs: {}", metadata.doc_url())?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_list() {
+        let list = rule_list().unwrap();
+        let truthy = list
+            .lines()
+            .find(|line| line.starts_with("truthy "))
+            .unwrap();
+        assert_eq!(
+            truthy.split_whitespace().collect::<Vec<_>>(),
+            ["truthy", "warning", "warning", "yes"]
+        );
+        assert!(list.contains("\ndocument-start "));
+    }
+
+    #[test]
+    fn test_explanation() {
+        let text = explanation("colons").unwrap();
+        assert!(text.starts_with("colons\n"));
+        assert!(text.contains("  max-spaces-after: int (default: 1)\n"));
+        assert!(text.contains("\nBad:\n    key :  value\n"));
+
+        let error = explanation("colon").unwrap_err().to_string();
+        assert!(error.contains("did you mean `colons`?"), "{}", error);
+    }
+}
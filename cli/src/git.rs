@@ -0,0 +1,250 @@
+//! Changed files and lines reported by git
+
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Which changes to ask git for
+#[derive(Debug, Clone)]
+pub enum Changes {
+    /// Changes in the working tree since a revision, plus untracked files
+    Since(String),
+    /// Changes staged in the index
+    Staged,
+}
+
+/// Files changed in a git repository and the line ranges changed in each
+#[derive(Debug)]
+pub struct ChangedFiles {
+    /// Changed lines of each file, keyed by canonical path
+    files: HashMap<PathBuf, Vec<RangeInclusive<usize>>>,
+}
+
+impl ChangedFiles {
+    /// Ask the `git` binary for the changes of the repository containing
+    /// the current directory
+    pub fn load(changes: &Changes) -> Result<Self> {
+        Self::load_in(Path::new("."), changes)
+    }
+
+    /// Ask the `git` binary for the changes of the repository containing
+    /// `dir`
+    fn load_in(dir: &Path, changes: &Changes) -> Result<Self> {
+        let root = PathBuf::from(git(dir, &["rev-parse", "--show-toplevel"])?.trim_end());
+
+        // Explicit prefixes, since `diff.noprefix` and `diff.mnemonicPrefix`
+        // change the ones `parse_diff` expects
+        let mut diff_args = vec![
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            "--unified=0",
+            "--diff-filter=ACMR",
+        ];
+        match changes {
+            Changes::Since(rev) => diff_args.extend(["--end-of-options", rev.as_str(), "--"]),
+            Changes::Staged => diff_args.extend(["--cached", "--"]),
+        }
+
+        let mut files = parse_diff(&git(dir, &diff_args)?, &root);
+
+        // Files git does not track yet are entirely new
+        if let Changes::Since(_) = changes {
+            let untracked = git(
+                dir,
+                &[
+                    "-c",
+                    "core.quotePath=false",
+                    "ls-files",
+                    "--others",
+                    "--exclude-standard",
+                    "--full-name",
+                    "--",
+                    &root.display().to_string(),
+                ],
+            )?;
+            for file in untracked.lines() {
+                files.insert(root.join(file), vec![1..=usize::MAX]);
+            }
+        }
+
+        // Match against the canonical paths of linted files
+        let files = files
+            .into_iter()
+            .map(|(path, lines)| (path.canonicalize().unwrap_or(path), lines))
+            .collect();
+        Ok(Self { files })
+    }
+
+    /// Check whether the file at `path` changed
+    pub fn contains(&self, path: &Path) -> bool {
+        self.lines(path).is_some()
+    }
+
+    /// Check whether `line` (1-based) of the file at `path` changed
+    pub fn contains_line(&self, path: &Path, line: usize) -> bool {
+        self.lines(path)
+            .is_some_and(|ranges| ranges.iter().any(|range| range.contains(&line)))
+    }
+
+    /// Changed line ranges of the file at `path`
+    fn lines(&self, path: &Path) -> Option<&Vec<RangeInclusive<usize>>> {
+        let path = path.canonicalize().ok()?;
+        self.files.get(&path)
+    }
+}
+
+/// Run git with `args` in `dir` and return its standard output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        bail!(
+            "git failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    String::from_utf8(output.stdout).context("git printed invalid UTF-8")
+}
+
+/// Parse the output of `git diff --unified=0` into the added line ranges of
+/// each file, with paths joined to the repository `root`
+///
+/// Files whose changes only delete lines are listed with no ranges.
+fn parse_diff(diff: &str, root: &Path) -> HashMap<PathBuf, Vec<RangeInclusive<usize>>> {
+    let mut files: HashMap<PathBuf, Vec<RangeInclusive<usize>>> = HashMap::new();
+    let mut current = None;
+    // Content lines may look like headers, e.g. `+++ b/x` adding `++ b/x`
+    let mut in_header = false;
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            in_header = true;
+            current = None;
+        } else if let (true, Some(path)) = (in_header, line.strip_prefix("+++ ")) {
+            current = path.strip_prefix("b/").map(|path| root.join(path));
+            if let Some(path) = &current {
+                files.entry(path.clone()).or_default();
+            }
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            in_header = false;
+            let (Some(path), Some(range)) = (&current, hunk_new_lines(hunk)) else {
+                continue;
+            };
+            files.entry(path.clone()).or_default().push(range);
+        }
+    }
+
+    files
+}
+
+/// Lines a hunk header such as `-3,2 +4,5 @@` adds, or `None` if it only
+/// deletes lines
+fn hunk_new_lines(hunk: &str) -> Option<RangeInclusive<usize>> {
+    let new = hunk
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match new.split_once(',') {
+        Some((start, count)) => (start.parse::<usize>().ok()?, count.parse::<usize>().ok()?),
+        None => (new.parse::<usize>().ok()?, 1),
+    };
+
+    if count == 0 {
+        return None;
+    }
+    Some(start..=start + count - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diff() {
+        let diff = "\
+diff --git a/k8s/app.yaml b/k8s/app.yaml
+index 1111111..2222222 100644
+--- a/k8s/app.yaml
++++ b/k8s/app.yaml
+@@ -3 +3 @@ kind: Deployment
+-  replicas: 1
++  replicas: 2
+@@ -10,0 +11,3 @@ spec:
++    a: 1
++++ b/not-a-file
++    c: 3
+@@ -20,2 +22,0 @@ spec:
+-    x: 1
+-    y: 2
+diff --git a/new.yml b/new.yml
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/new.yml
+@@ -0,0 +1,2 @@
++a: 1
++b: 2
+diff --git a/trimmed.yaml b/trimmed.yaml
+--- a/trimmed.yaml
++++ b/trimmed.yaml
+@@ -5 +4,0 @@ key:
+-  old: 1
+";
+        let files = parse_diff(diff, Path::new("/repo"));
+
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[Path::new("/repo/k8s/app.yaml")], [3..=3, 11..=13]);
+        assert_eq!(files[Path::new("/repo/new.yml")], [1..=2]);
+        assert!(files[Path::new("/repo/trimmed.yaml")].is_empty());
+    }
+
+    #[test]
+    fn test_load_ignores_diff_prefix_config() {
+        let dir = std::env::temp_dir().join(format!("yaml-lint-git-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let run = |args: &[&str]| git(&dir, args).unwrap();
+        run(&["init", "--quiet"]);
+        run(&["config", "diff.noprefix", "true"]);
+        run(&["config", "user.name", "test"]);
+        run(&["config", "user.email", "test@example.com"]);
+        std::fs::write(dir.join("app.yaml"), "a: 1\n").unwrap();
+        run(&["add", "app.yaml"]);
+        run(&["commit", "--quiet", "-m", "init"]);
+        std::fs::write(dir.join("app.yaml"), "a: 1\nb: 2\n").unwrap();
+
+        let changed = ChangedFiles::load_in(&dir, &Changes::Since("HEAD".to_string())).unwrap();
+        assert!(changed.contains_line(&dir.join("app.yaml"), 2));
+        assert!(!changed.contains_line(&dir.join("app.yaml"), 1));
+
+        run(&["config", "diff.noprefix", "false"]);
+        run(&["config", "diff.mnemonicPrefix", "true"]);
+        let changed = ChangedFiles::load_in(&dir, &Changes::Since("HEAD".to_string())).unwrap();
+        assert!(changed.contains_line(&dir.join("app.yaml"), 2));
+
+        // Revisions are never taken as options
+        assert!(ChangedFiles::load_in(&dir, &Changes::Since("--output=x".to_string())).is_err());
+        assert!(!dir.join("x").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_hunk_new_lines() {
+        assert_eq!(hunk_new_lines("-1 +1 @@"), Some(1..=1));
+        assert_eq!(hunk_new_lines("-1,2 +4,3 @@ key:"), Some(4..=6));
+        assert_eq!(hunk_new_lines("-4,2 +3,0 @@"), None);
+        assert_eq!(hunk_new_lines("garbage"), None);
+    }
+}
@@ -0,0 +1,788 @@
+//! Language server speaking LSP over stdio
+//!
+//! Publishes the problems of open documents as diagnostics, offers their
+//! fixes and `# yamllint disable-line` directives as code actions, and
+//! reloads the configuration when a config file changes.
+
+use crate::watch::{canonical_path, is_config_file};
+use crate::{Cli, Linters, load_config};
+use anyhow::{Context, Result};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+    DidSaveTextDocument, Notification as _, PublishDiagnostics, ShowMessage,
+};
+use lsp_types::request::{CodeActionRequest, RegisterCapability, Request as _};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, Diagnostic, DiagnosticSeverity,
+    DidChangeWatchedFilesRegistrationOptions, FileSystemWatcher, GlobPattern, InitializeParams,
+    InitializeResult, MessageType, NumberOrString, OneOf, Position, PublishDiagnosticsParams,
+    Range, Registration, RegistrationParams, RelativePattern, ServerCapabilities, ServerInfo,
+    ShowMessageParams, TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Uri, WorkspaceEdit,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use yaml_lint_core::config::CONFIG_FILE_NAMES;
+use yaml_lint_core::directives::disable_line_comment;
+use yaml_lint_core::{Fixer, LintLevel, LintProblem, TextEdit};
+
+/// Code action kind of the action fixing every fixable problem
+const FIX_ALL_KIND: &str = "source.fixAll.yaml-lint";
+
+/// Outcome of a request, or its JSON-RPC error code and message
+type RequestResult = std::result::Result<Value, (ErrorCode, String)>;
+
+/// Serve the editor on stdin and stdout until it asks the server to exit
+pub fn run(cli: &Cli) -> Result<()> {
+    let linters = load_config(cli)?;
+    let (connection, io_threads) = Connection::stdio();
+
+    let shutdown = Server::new(cli, linters, &connection).serve()?;
+    drop(connection);
+    io_threads
+        .join()
+        .context("Failed to close the LSP connection")?;
+
+    // The protocol asks for a failure code when exiting without a shutdown
+    if !shutdown {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// A document opened in the editor
+struct Document {
+    path: PathBuf,
+    version: Option<i32>,
+    text: String,
+    problems: Vec<LintProblem>,
+}
+
+/// Language server state, exchanging messages over `connection`
+struct Server<'a> {
+    cli: &'a Cli,
+    linters: Linters,
+    documents: HashMap<Uri, Document>,
+    connection: &'a Connection,
+    shutdown: bool,
+}
+
+impl<'a> Server<'a> {
+    fn new(cli: &'a Cli, linters: Linters, connection: &'a Connection) -> Self {
+        Self {
+            cli,
+            linters,
+            documents: HashMap::new(),
+            connection,
+            shutdown: false,
+        }
+    }
+
+    /// Initialize the session, then handle messages until the `exit`
+    /// notification or the end of the input, and return whether a shutdown
+    /// was requested first
+    fn serve(&mut self) -> Result<bool> {
+        let (id, params) = self.connection.initialize_start()?;
+        let params: InitializeParams =
+            serde_json::from_value(params).context("Invalid initialize params")?;
+        self.connection
+            .initialize_finish(id, serde_json::to_value(initialize_result())?)?;
+
+        // Whether the client can watch config files for the server
+        let watch_registration = params
+            .capabilities
+            .workspace
+            .and_then(|workspace| workspace.did_change_watched_files)
+            .and_then(|watched| watched.dynamic_registration)
+            .unwrap_or(false);
+        if watch_registration {
+            self.register_config_watcher()?;
+        }
+
+        for message in &self.connection.receiver {
+            match message {
+                Message::Request(request) => self.handle_request(request)?,
+                Message::Notification(notification) if notification.method == "exit" => break,
+                Message::Notification(notification) => self.notification(notification)?,
+                // Answers to requests sent by the server
+                Message::Response(_) => {}
+            }
+        }
+        Ok(self.shutdown)
+    }
+
+    fn handle_request(&mut self, request: Request) -> Result<()> {
+        let result = if self.shutdown {
+            Err((
+                ErrorCode::InvalidRequest,
+                "the server is shutting down".to_string(),
+            ))
+        } else {
+            self.request(&request)
+        };
+
+        let response = match result {
+            Ok(result) => Response::new_ok(request.id, result),
+            Err((code, message)) => Response::new_err(request.id, code as i32, message),
+        };
+        self.send(response)
+    }
+
+    fn request(&mut self, request: &Request) -> RequestResult {
+        match request.method.as_str() {
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            CodeActionRequest::METHOD => {
+                let params = serde_json::from_value(request.params.clone())
+                    .map_err(|e| (ErrorCode::InvalidParams, e.to_string()))?;
+                let actions = self.code_actions(params)?;
+                serde_json::to_value(actions).map_err(|e| (ErrorCode::InternalError, e.to_string()))
+            }
+            method => Err((
+                ErrorCode::MethodNotFound,
+                format!("unknown method {}", method),
+            )),
+        }
+    }
+
+    fn notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = extract::<DidOpenTextDocument>(notification)?;
+                let document = params.text_document;
+                self.documents.insert(
+                    document.uri.clone(),
+                    Document {
+                        path: document_path(&document.uri),
+                        version: Some(document.version),
+                        text: document.text,
+                        problems: Vec::new(),
+                    },
+                );
+                self.publish(&document.uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = extract::<DidChangeTextDocument>(notification)?;
+                let uri = params.text_document.uri;
+                // Changes carry the full text with full document sync
+                let text = params.content_changes.into_iter().last();
+                if let (Some(document), Some(change)) = (self.documents.get_mut(&uri), text) {
+                    document.text = change.text;
+                    document.version = Some(params.text_document.version);
+                }
+                self.publish(&uri)
+            }
+            DidSaveTextDocument::METHOD => {
+                let uri = extract::<DidSaveTextDocument>(notification)?
+                    .text_document
+                    .uri;
+                if is_config_file(self.cli, &document_path(&uri)) {
+                    self.reload_config()
+                } else {
+                    self.publish(&uri)
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let uri = extract::<DidCloseTextDocument>(notification)?
+                    .text_document
+                    .uri;
+                self.documents.remove(&uri);
+                self.send_diagnostics(uri, None, Vec::new())
+            }
+            DidChangeWatchedFiles::METHOD => {
+                let params = extract::<DidChangeWatchedFiles>(notification)?;
+                let config_changed = params
+                    .changes
+                    .iter()
+                    .any(|change| is_config_file(self.cli, &document_path(&change.uri)));
+                if config_changed {
+                    self.reload_config()
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Ask the client to report changes to config files anywhere in the
+    /// workspace, and to the config given with `--config`
+    fn register_config_watcher(&mut self) -> Result<()> {
+        let mut watchers: Vec<FileSystemWatcher> = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| FileSystemWatcher {
+                glob_pattern: GlobPattern::String(format!("**/{}", name)),
+                kind: None,
+            })
+            .collect();
+
+        // The config may live outside the workspace, so it is watched
+        // relative to its own directory
+        let config = self.cli.config.as_deref().map(canonical_path);
+        let config_pattern = config.as_deref().and_then(|config| {
+            Some(RelativePattern {
+                base_uri: OneOf::Right(path_to_uri(config.parent()?)?),
+                pattern: config.file_name()?.to_str()?.to_string(),
+            })
+        });
+        if let Some(pattern) = config_pattern {
+            watchers.push(FileSystemWatcher {
+                glob_pattern: GlobPattern::Relative(pattern),
+                kind: None,
+            });
+        }
+
+        let registration = Registration {
+            id: "yaml-lint-config-files".to_string(),
+            method: DidChangeWatchedFiles::METHOD.to_string(),
+            register_options: Some(serde_json::to_value(
+                DidChangeWatchedFilesRegistrationOptions { watchers },
+            )?),
+        };
+
+        self.send(Request::new(
+            RequestId::from("register-config-watcher".to_string()),
+            RegisterCapability::METHOD.to_string(),
+            RegistrationParams {
+                registrations: vec![registration],
+            },
+        ))
+    }
+
+    /// Reload the configuration and lint every open document again
+    ///
+    /// The previous configuration stays in effect while the new one is
+    /// invalid, e.g. half-way through editing it.
+    fn reload_config(&mut self) -> Result<()> {
+        match load_config(self.cli) {
+            Ok(linters) => self.linters = linters,
+            Err(e) => return self.show_error(format!("Error reloading config: {:#}", e)),
+        }
+
+        let mut uris: Vec<Uri> = self.documents.keys().cloned().collect();
+        uris.sort();
+        for uri in uris {
+            self.publish(&uri)?;
+        }
+        Ok(())
+    }
+
+    /// Lint the document at `uri` and publish its problems
+    fn publish(&mut self, uri: &Uri) -> Result<()> {
+        let Some(document) = self.documents.get_mut(uri) else {
+            return Ok(());
+        };
+
+        // Files the config ignores have no problems
+        let linted = self.linters.for_file(&document.path).and_then(|linter| {
+            if linter.config().is_ignored(&document.path) {
+                return Ok(Vec::new());
+            }
+            Ok(linter.lint_content(&document.path, &document.text)?)
+        });
+        let error = match linted {
+            Ok(problems) => {
+                document.problems = problems;
+                None
+            }
+            Err(e) => {
+                document.problems.clear();
+                Some(format!(
+                    "Error linting {}: {:#}",
+                    document.path.display(),
+                    e
+                ))
+            }
+        };
+
+        let index = LineIndex::new(&document.text);
+        let diagnostics = document
+            .problems
+            .iter()
+            .map(|problem| diagnostic(problem, &index))
+            .collect();
+        let version = document.version;
+
+        if let Some(error) = error {
+            self.show_error(error)?;
+        }
+        self.send_diagnostics(uri.clone(), version, diagnostics)
+    }
+
+    fn send_diagnostics(
+        &mut self,
+        uri: Uri,
+        version: Option<i32>,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<()> {
+        self.notify::<PublishDiagnostics>(PublishDiagnosticsParams::new(uri, diagnostics, version))
+    }
+
+    /// Quick fixes for the problems in the requested range, and an action
+    /// fixing every fixable problem of the document
+    fn code_actions(
+        &mut self,
+        params: CodeActionParams,
+    ) -> std::result::Result<Vec<CodeActionOrCommand>, (ErrorCode, String)> {
+        let uri = params.text_document.uri;
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(Vec::new());
+        };
+
+        let first_line = params.range.start.line as usize;
+        let last_line = params.range.end.line as usize;
+
+        // Kinds the client asked for, all kinds without a filter
+        let only = params.context.only.unwrap_or_default();
+        let wanted = |kind: &str| {
+            only.is_empty()
+                || only.iter().any(|only| {
+                    kind == only.as_str()
+                        || kind
+                            .strip_prefix(only.as_str())
+                            .is_some_and(|rest| rest.starts_with('.'))
+                })
+        };
+
+        let index = LineIndex::new(&document.text);
+        let mut actions = Vec::new();
+
+        if wanted(CodeActionKind::QUICKFIX.as_str()) {
+            let in_range = document.problems.iter().filter(|problem| {
+                (first_line..=last_line).contains(&problem.line.saturating_sub(1))
+            });
+            for problem in in_range {
+                let diagnostics = vec![diagnostic(problem, &index)];
+                if let Some(fix) = &problem.fix {
+                    actions.push(code_action(
+                        format!("Fix: {} ({})", problem.message, problem.rule),
+                        CodeActionKind::QUICKFIX,
+                        diagnostics.clone(),
+                        &uri,
+                        &index,
+                        &fix.edits,
+                    ));
+                }
+                actions.push(code_action(
+                    format!("Disable {} for this line", problem.rule),
+                    CodeActionKind::QUICKFIX,
+                    diagnostics,
+                    &uri,
+                    &index,
+                    &[disable_line_edit(&document.text, &index, problem)],
+                ));
+            }
+        }
+
+        if wanted(FIX_ALL_KIND) && !document.problems.is_empty() {
+            let registry = self
+                .linters
+                .for_file(&document.path)
+                .and_then(|linter| Ok(linter.registry_for_path(&document.path)?))
+                .map_err(|e| (ErrorCode::InvalidRequest, format!("{:#}", e)))?;
+            let result =
+                Fixer::new(&registry).fix(&document.path.display().to_string(), &document.text);
+            if let Some(fixed) = result.fixed_content.filter(|fixed| *fixed != document.text) {
+                let edit = TextEdit::new(0..document.text.len(), fixed);
+                actions.push(code_action(
+                    "Fix all auto-fixable problems".to_string(),
+                    CodeActionKind::new(FIX_ALL_KIND),
+                    Vec::new(),
+                    &uri,
+                    &index,
+                    &[edit],
+                ));
+            }
+        }
+
+        Ok(actions)
+    }
+
+    fn show_error(&mut self, message: String) -> Result<()> {
+        self.notify::<ShowMessage>(ShowMessageParams {
+            typ: MessageType::ERROR,
+            message,
+        })
+    }
+
+    fn notify<N: lsp_types::notification::Notification>(
+        &mut self,
+        params: N::Params,
+    ) -> Result<()> {
+        self.send(Notification::new(N::METHOD.to_string(), params))
+    }
+
+    fn send(&mut self, message: impl Into<Message>) -> Result<()> {
+        self.connection
+            .sender
+            .send(message.into())
+            .context("Failed to write LSP message")
+    }
+}
+
+/// Parameters of a notification sent by the client
+fn extract<N: lsp_types::notification::Notification>(
+    notification: Notification,
+) -> Result<N::Params> {
+    notification
+        .extract(N::METHOD)
+        .with_context(|| format!("Invalid {} params", N::METHOD))
+}
+
+/// Capabilities announced in the response to `initialize`
+fn initialize_result() -> InitializeResult {
+    InitializeResult {
+        capabilities: ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Options(
+                TextDocumentSyncOptions {
+                    open_close: Some(true),
+                    change: Some(TextDocumentSyncKind::FULL),
+                    save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                    ..Default::default()
+                },
+            )),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![
+                    CodeActionKind::QUICKFIX,
+                    CodeActionKind::new(FIX_ALL_KIND),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        },
+        server_info: Some(ServerInfo {
+            name: "yaml-lint".to_string(),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        }),
+    }
+}
+
+/// Diagnostic reporting `problem`
+fn diagnostic(problem: &LintProblem, index: &LineIndex) -> Diagnostic {
+    let severity = match problem.level {
+        LintLevel::Error => DiagnosticSeverity::ERROR,
+        LintLevel::Warning => DiagnosticSeverity::WARNING,
+    };
+    Diagnostic {
+        range: index.range(problem_span(problem, index)),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(problem.rule.clone())),
+        source: Some("yaml-lint".to_string()),
+        message: problem.message.clone(),
+        ..Default::default()
+    }
+}
+
+/// Byte range of `problem`, empty at its position if it has no span
+fn problem_span(problem: &LintProblem, index: &LineIndex) -> std::ops::Range<usize> {
+    problem.span.clone().unwrap_or_else(|| {
+        let start = index.offset(
+            problem.line.saturating_sub(1),
+            problem.column.saturating_sub(1),
+        );
+        start..start
+    })
+}
+
+fn code_action(
+    title: String,
+    kind: CodeActionKind,
+    diagnostics: Vec<Diagnostic>,
+    uri: &Uri,
+    index: &LineIndex,
+    edits: &[TextEdit],
+) -> CodeActionOrCommand {
+    let edits = edits
+        .iter()
+        .map(|edit| lsp_types::TextEdit {
+            range: index.range(edit.range.clone()),
+            new_text: edit.replacement.clone(),
+        })
+        .collect();
+
+    CodeActionOrCommand::CodeAction(CodeAction {
+        title,
+        kind: Some(kind),
+        diagnostics: Some(diagnostics),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+/// Edit disabling the rule of `problem` on its line with a directive on the
+/// line above, extending a directive already there
+fn disable_line_edit(text: &str, index: &LineIndex, problem: &LintProblem) -> TextEdit {
+    let line = problem.line.saturating_sub(1);
+    let line_text = |line: usize| {
+        let start = index.offset(line, 0);
+        (start, text[start..].lines().next().unwrap_or(""))
+    };
+
+    let (start, current) = line_text(line);
+    if line > 0 {
+        let (previous_start, previous) = line_text(line - 1);
+        if previous
+            .trim_start()
+            .starts_with("# yamllint disable-line rule:")
+        {
+            let end = previous_start + previous.trim_end().len();
+            return TextEdit::insert(end, format!(" rule:{}", problem.rule));
+        }
+    }
+
+    let indent = &current[..current.len() - current.trim_start().len()];
+    let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    TextEdit::insert(
+        start,
+        format!(
+            "{}{}{}",
+            indent,
+            disable_line_comment(&problem.rule),
+            newline
+        ),
+    )
+}
+
+/// Path of the document at `uri`; documents that aren't files, e.g.
+/// unsaved ones, use the URI itself, so only the fallback config applies
+fn document_path(uri: &Uri) -> PathBuf {
+    uri_to_path(uri.as_str()).unwrap_or_else(|| PathBuf::from(uri.as_str()))
+}
+
+/// Path of a `file://` URI
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // Skip the authority, usually empty or `localhost`
+    let path = &rest[rest.find('/')?..];
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut iter = path.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+/// `file://` URI of an absolute path
+fn path_to_uri(path: &Path) -> Option<Uri> {
+    let mut uri = "file://".to_string();
+    for byte in path.to_str()?.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'.' | b'_' | b'~' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri.parse().ok()
+}
+
+/// Converts byte offsets of a document to LSP positions, whose characters
+/// count UTF-16 code units
+struct LineIndex<'a> {
+    text: &'a str,
+    /// Byte offset at which each line starts
+    starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(text: &'a str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+        Self { text, starts }
+    }
+
+    /// Byte offset of a 0-based line and byte column, clamped to the line
+    fn offset(&self, line: usize, column: usize) -> usize {
+        let Some(&start) = self.starts.get(line) else {
+            return self.text.len();
+        };
+        let end = self
+            .starts
+            .get(line + 1)
+            .map_or(self.text.len(), |next| next - 1);
+        (start + column).min(end)
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.starts.partition_point(|start| *start <= offset) - 1;
+        let character = self.text[self.starts[line]..offset].encode_utf16().count();
+
+        Position::new(line as u32, character as u32)
+    }
+
+    fn range(&self, range: std::ops::Range<usize>) -> Range {
+        Range::new(self.position(range.start), self.position(range.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use serde_json::json;
+    use yaml_lint_core::Fix;
+
+    fn request(id: i32, method: &str, params: Value) -> Message {
+        Request::new(id.into(), method.to_string(), params).into()
+    }
+
+    fn notification(method: &str, params: Value) -> Message {
+        Notification::new(method.to_string(), params).into()
+    }
+
+    #[test]
+    fn test_session() {
+        let cli = Cli::parse_from(["yaml-lint", "lsp", "-d", "default"]);
+        let linters = load_config(&cli).unwrap();
+        let uri = "file:///tmp/yaml-lint-lsp/app.yaml";
+        let (server, client) = Connection::memory();
+
+        let messages = [
+            request(1, "initialize", json!({ "capabilities": {} })),
+            notification("initialized", json!({})),
+            notification(
+                "textDocument/didOpen",
+                json!({ "textDocument": {
+                    "uri": uri,
+                    "languageId": "yaml",
+                    "version": 1,
+                    "text": "---\nkey: value  \n",
+                } }),
+            ),
+            request(
+                2,
+                "textDocument/codeAction",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "range": {
+                        "start": { "line": 1, "character": 0 },
+                        "end": { "line": 1, "character": 0 },
+                    },
+                    "context": { "diagnostics": [] },
+                }),
+            ),
+            request(3, "unknown/method", Value::Null),
+            request(4, "shutdown", Value::Null),
+            notification("exit", Value::Null),
+        ];
+        for message in messages {
+            client.sender.send(message).unwrap();
+        }
+
+        let shutdown = Server::new(&cli, linters, &server).serve().unwrap();
+        assert!(shutdown);
+
+        let messages: Vec<Value> = client
+            .receiver
+            .try_iter()
+            .map(|message| serde_json::to_value(message).unwrap())
+            .collect();
+        assert_eq!(messages.len(), 5);
+
+        let capabilities = &messages[0]["result"]["capabilities"];
+        assert_eq!(capabilities["textDocumentSync"]["change"], json!(1));
+
+        let diagnostics = &messages[1]["params"]["diagnostics"];
+        assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+        assert_eq!(diagnostics[0]["code"], json!("trailing-spaces"));
+        assert_eq!(
+            diagnostics[0]["range"]["start"],
+            json!({ "line": 1, "character": 10 })
+        );
+
+        let titles: Vec<&str> = messages[2]["result"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|action| action["title"].as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            [
+                "Fix: trailing spaces (trailing-spaces)",
+                "Disable trailing-spaces for this line",
+                "Fix all auto-fixable problems",
+            ]
+        );
+
+        assert_eq!(
+            messages[3]["error"]["code"],
+            json!(ErrorCode::MethodNotFound as i32)
+        );
+        assert!(messages[4]["result"].is_null());
+    }
+
+    #[test]
+    fn test_disable_line_edit() {
+        let problem = |line| LintProblem::new(line, 1, "message", "truthy", LintLevel::Error);
+
+        let text = "a:\n  b: yes\n";
+        let edit = disable_line_edit(text, &LineIndex::new(text), &problem(2));
+        assert_eq!(
+            Fix::new(vec![edit]).apply(text),
+            "a:\n  # yamllint disable-line rule:truthy\n  b: yes\n"
+        );
+
+        let text = "# yamllint disable-line rule:colons\nb:  yes\n";
+        let edit = disable_line_edit(text, &LineIndex::new(text), &problem(2));
+        assert_eq!(
+            Fix::new(vec![edit]).apply(text),
+            "# yamllint disable-line rule:colons rule:truthy\nb:  yes\n"
+        );
+    }
+
+    #[test]
+    fn test_positions_count_utf16() {
+        let text = "a: \u{e9}\u{1f600}x\nb\n";
+        let index = LineIndex::new(text);
+
+        assert_eq!(index.position(text.find('x').unwrap()).character, 6);
+        assert_eq!(index.position(text.find('b').unwrap()).line, 1);
+        assert_eq!(index.offset(0, 100), text.find('\n').unwrap());
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///home/me/my%20app/a.yaml"),
+            Some(PathBuf::from("/home/me/my app/a.yaml"))
+        );
+        assert_eq!(
+            uri_to_path("file://localhost/etc/a.yaml"),
+            Some(PathBuf::from("/etc/a.yaml"))
+        );
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+        assert_eq!(uri_to_path("file:///bad%2"), None);
+    }
+
+    #[test]
+    fn test_path_to_uri() {
+        let uri = path_to_uri(Path::new("/home/me/my app/a.yaml")).unwrap();
+        assert_eq!(uri.as_str(), "file:///home/me/my%20app/a.yaml");
+        assert_eq!(
+            uri_to_path(uri.as_str()),
+            Some(PathBuf::from("/home/me/my app/a.yaml"))
+        );
+    }
+}
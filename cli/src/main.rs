@@ -1,13 +1,25 @@
 //! YAML Linter CLI
 
+mod explain;
+mod git;
+mod lsp;
+mod watch;
+
 use anyhow::{Context, Result};
-use clap::{Parser, ValueEnum};
+use clap::{ArgGroup, CommandFactory, Parser, Subcommand, ValueEnum};
+use git::{ChangedFiles, Changes};
+use ignore::WalkBuilder;
 use is_terminal::IsTerminal;
+use std::ffi::OsString;
 use std::fs;
 use std::io;
-use std::path::PathBuf;
-use walkdir::WalkDir;
-use yaml_lint_core::{Config, Fixer, LintLevel, Linter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use yaml_lint_core::compat::CompatKind;
+use yaml_lint_core::config::CONFIG_FILE_NAMES;
+use yaml_lint_core::init::starter_config;
+use yaml_lint_core::output::{colorize_diff, unified_diff};
+use yaml_lint_core::{Baseline, Config, ConfigResolver, Fixer, LintLevel, Linter};
 
 /// Color mode for output
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
@@ -24,16 +36,21 @@ pub enum ColorMode {
 #[derive(Parser)]
 #[command(name = "yaml-lint")]
 #[command(version, about = "A fast YAML linter written in Rust", long_about = None)]
+#[command(group(ArgGroup::new("changes").args(["changed_since", "staged"])))]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Files or directories to lint
     #[arg(required = true)]
     paths: Vec<PathBuf>,
 
     /// Path to config file
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     config: Option<PathBuf>,
 
-    /// Output format (standard, colored, parsable)
+    /// Output format (standard, colored, parsable, codeframe)
     #[arg(short = 'f', long, default_value = "standard")]
     format: String,
 
@@ -46,33 +63,245 @@ struct Cli {
     strict: bool,
 
     /// Preset to use (default, relaxed)
-    #[arg(short = 'd', long)]
+    #[arg(short = 'd', long, global = true)]
     preset: Option<String>,
 
     /// List files that would be linted
     #[arg(long)]
     list_files: bool,
 
+    /// Also lint files excluded by .gitignore, .ignore and git exclude files
+    #[arg(long)]
+    no_ignore: bool,
+
+    /// Also lint hidden files and directories
+    #[arg(long)]
+    hidden: bool,
+
+    /// Only lint files changed since the git revision REV, including
+    /// uncommitted and untracked files
+    #[arg(long, value_name = "REV")]
+    changed_since: Option<String>,
+
+    /// Only lint files with changes staged in git
+    #[arg(long)]
+    staged: bool,
+
+    /// Only report problems on lines changed according to --changed-since
+    /// or --staged
+    #[arg(long, requires = "changes")]
+    changed_lines_only: bool,
+
+    /// Only report problems not recorded in the baseline FILE
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Record the current problems in the baseline FILE instead of reporting
+    /// them; with --baseline, only the problems still in that baseline
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["fix", "dry_run"])]
+    write_baseline: Option<PathBuf>,
+
+    /// Keep running and re-lint files as they change, printing only the
+    /// problems that appear or go away
+    #[arg(long, conflicts_with_all = [
+        "fix",
+        "dry_run",
+        "list_files",
+        "print_config_path",
+        "print_config",
+        "changes",
+        "baseline",
+        "write_baseline",
+    ])]
+    watch: bool,
+
+    /// Clear the screen before each round of --watch output
+    #[arg(long, requires = "watch")]
+    clear: bool,
+
     /// Auto-fix problems where possible
     #[arg(long)]
     fix: bool,
 
-    /// Show what would be fixed without making changes
+    /// Print a unified diff of what --fix would change without writing files;
+    /// exits non-zero if any file would change
     #[arg(long)]
     dry_run: bool,
+
+    /// Print the config file used for each file instead of linting
+    #[arg(long)]
+    print_config_path: bool,
+
+    /// Print the effective config for each file, with overrides applied and
+    /// option defaults filled in, instead of linting
+    #[arg(long)]
+    print_config: bool,
+}
+
+/// Commands run instead of linting
+#[derive(Subcommand)]
+enum Command {
+    /// Run a language server over stdio, for editor integration
+    Lsp,
+    /// List the rules with their level in each preset and whether they are
+    /// fixable
+    Rules,
+    /// Show what a rule checks, its options and examples
+    Explain {
+        /// Name of the rule
+        rule: String,
+    },
+    /// Write a starter .yamllint matching the conventions of existing files
+    Init {
+        /// Files or directories to scan
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+
+        /// Overwrite an existing .yamllint
+        #[arg(long)]
+        force: bool,
+    },
+    /// Inspect config files
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+/// Subcommands of `config`
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Report the yamllint keys of a config that are unsupported or behave
+    /// differently, exiting with 1 if there are any
+    Check {
+        /// Config file to check, by default the one used for the current
+        /// directory
+        file: Option<PathBuf>,
+    },
+}
+
+/// Parse the command line, linting the given paths rather than running a
+/// subcommand when they all exist, relative to `dir`: `yaml-lint config`
+/// lints a `config/` directory
+fn parse_cli<I, T>(args: I, dir: &Path) -> Cli
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString>,
+{
+    let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    parse_paths_named_like_commands(&args, dir).unwrap_or_else(|| Cli::parse_from(args))
+}
+
+/// Parse `args` as paths to lint if the first of them is named like a
+/// subcommand and all of them exist relative to `dir`
+fn parse_paths_named_like_commands(args: &[OsString], dir: &Path) -> Option<Cli> {
+    let command = Cli::command();
+    let positionals = positional_indices(&command, args);
+    let &first = positionals.first()?;
+    let name = args[first].to_str()?;
+    if command.find_subcommand(name).is_none()
+        || !positionals.iter().all(|&idx| dir.join(&args[idx]).exists())
+    {
+        return None;
+    }
+
+    // `./config` names the same path without matching the subcommand
+    let mut args = args.to_vec();
+    args[first] = Path::new(".").join(name).into_os_string();
+    let mut cli = Cli::try_parse_from(args).ok()?;
+    cli.paths[0] = PathBuf::from(name);
+    Some(cli)
+}
+
+/// Indices of the arguments that are neither options nor option values
+fn positional_indices(command: &clap::Command, args: &[OsString]) -> Vec<usize> {
+    let takes_value = |matches: &dyn Fn(&clap::Arg) -> bool| {
+        command
+            .get_arguments()
+            .any(|arg| matches(arg) && arg.get_action().takes_values())
+    };
+
+    let mut indices = Vec::new();
+    let mut idx = 1;
+    while idx < args.len() {
+        let arg = args[idx].to_string_lossy();
+        if arg == "--" {
+            break;
+        }
+        if let Some(long) = arg.strip_prefix("--") {
+            if !long.contains('=') && takes_value(&|a| a.get_long() == Some(long)) {
+                idx += 1;
+            }
+        } else if let Some(shorts) = arg.strip_prefix('-').filter(|s| !s.is_empty()) {
+            // A short option's value is the rest of the argument or the next one
+            let value_at = shorts
+                .chars()
+                .position(|c| takes_value(&|a| a.get_short() == Some(c)));
+            if value_at == Some(shorts.chars().count() - 1) {
+                idx += 1;
+            }
+        } else {
+            indices.push(idx);
+        }
+        idx += 1;
+    }
+    indices
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let cli = parse_cli(std::env::args_os(), Path::new(""));
+
+    match &cli.command {
+        Some(Command::Lsp) => return lsp::run(&cli),
+        Some(Command::Rules) => {
+            print!("{}", explain::rule_list()?);
+            return Ok(());
+        }
+        Some(Command::Explain { rule }) => {
+            print!("{}", explain::explanation(rule)?);
+            return Ok(());
+        }
+        Some(Command::Init { paths, force }) => return run_init(&cli, paths, *force),
+        Some(Command::Config {
+            command: ConfigCommand::Check { file },
+        }) => return run_config_check(&cli, file.as_deref()),
+        None => {}
+    }
 
     // Configure color output
     configure_colors(&cli.color);
 
     // Load configuration
-    let config = load_config(&cli)?;
+    let mut linters = load_config(&cli)?;
 
     // Collect YAML files
-    let yaml_files = collect_yaml_files(&cli.paths)?;
+    let mut yaml_files = collect_yaml_files(&cli, &mut linters)?;
+
+    // Baseline files are generated, don't lint them
+    let baseline_files: Vec<PathBuf> = [&cli.baseline, &cli.write_baseline]
+        .into_iter()
+        .flatten()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect();
+    if !baseline_files.is_empty() {
+        yaml_files.retain(|file| {
+            file.canonicalize()
+                .map_or(true, |file| !baseline_files.contains(&file))
+        });
+    }
+
+    // Restrict to the files changed in git
+    let changes = match (&cli.changed_since, cli.staged) {
+        (Some(rev), _) => Some(Changes::Since(rev.clone())),
+        (None, true) => Some(Changes::Staged),
+        (None, false) => None,
+    };
+    let changed_files = changes
+        .map(|changes| ChangedFiles::load(&changes))
+        .transpose()?;
+    if let Some(changed_files) = &changed_files {
+        yaml_files.retain(|file| changed_files.contains(file));
+    }
 
     if cli.list_files {
         for file in &yaml_files {
@@ -81,36 +310,99 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if cli.print_config_path {
+        for file in &yaml_files {
+            println!("{}: {}", file.display(), linters.config_source_for(file)?);
+        }
+        return Ok(());
+    }
+
+    if cli.print_config {
+        for (index, file) in yaml_files.iter().enumerate() {
+            if index > 0 {
+                println!();
+            }
+            println!("# {}: {}", file.display(), linters.config_source_for(file)?);
+            let config = linters.for_file(file)?.config().for_path(file);
+            print!("{}", config.to_yaml());
+        }
+        return Ok(());
+    }
+
     // Handle fix mode
     if cli.fix || cli.dry_run {
-        return run_fix_mode(&cli, &config, &yaml_files);
+        return run_fix_mode(&cli, &mut linters, &yaml_files);
     }
 
-    // Create linter for normal mode
-    let linter = Linter::new(config);
-
     // Parse output format, defaulting to colored if TTY and not explicitly set
-    let format: yaml_lint_core::output::OutputFormat =
-        if cli.format == "standard" && should_use_colors(&cli.color) {
+    let format: yaml_lint_core::output::OutputFormat = match cli.format.as_str() {
+        "standard" if should_use_colors(&cli.color) => {
             yaml_lint_core::output::OutputFormat::Colored
-        } else {
-            cli.format
-                .parse()
-                .map_err(|e| anyhow::anyhow!("Invalid format: {}", e))?
-        };
+        }
+        "codeframe" if should_use_colors(&cli.color) => {
+            yaml_lint_core::output::OutputFormat::ColoredCodeFrame
+        }
+        _ => cli
+            .format
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid format: {}", e))?,
+    };
 
     let formatter = format.formatter();
 
+    if cli.watch {
+        return watch::run(&cli, linters, yaml_files, formatter.as_ref());
+    }
+
+    let mut baseline = cli
+        .baseline
+        .as_deref()
+        .map(|path| {
+            Baseline::load(path)
+                .with_context(|| format!("Failed to load baseline {}", path.display()))
+        })
+        .transpose()?;
+    let mut new_baseline = cli.write_baseline.as_deref().map(Baseline::new);
+
     // Lint all files
     let mut has_errors = false;
     let mut has_warnings = false;
     let mut total_problems = 0;
 
     for file in &yaml_files {
-        match linter.lint_file(file) {
-            Ok(problems) => {
+        let linter = linters.for_file(file)?;
+
+        // Read the file here so formatters can show source snippets
+        let result = fs::read_to_string(file)
+            .map_err(yaml_lint_core::LintError::from)
+            .and_then(|content| Ok((linter.lint_content(file, &content)?, content)));
+
+        match result {
+            Ok((mut problems, content)) => {
+                if let (Some(changed_files), true) = (&changed_files, cli.changed_lines_only) {
+                    problems.retain(|problem| changed_files.contains_line(file, problem.line));
+                }
+
+                if let Some(new_baseline) = &mut new_baseline {
+                    // Updating a baseline drops fixed problems but never
+                    // accepts new ones
+                    let accepted = match &mut baseline {
+                        Some(baseline) => baseline.filter(file, &content, problems).1,
+                        None => problems,
+                    };
+                    new_baseline.add(file, &content, &accepted);
+                    continue;
+                }
+                if let Some(baseline) = &mut baseline {
+                    problems = baseline.filter(file, &content, problems).0;
+                }
+
                 if !problems.is_empty() {
-                    let output = formatter.format_problems(&problems, &file.display().to_string());
+                    let output = formatter.format_problems_with_source(
+                        &problems,
+                        &file.display().to_string(),
+                        &content,
+                    );
                     print!("{}", output);
 
                     for problem in &problems {
@@ -130,6 +422,39 @@ fn main() -> Result<()> {
         }
     }
 
+    if let (Some(new_baseline), Some(path)) = (&new_baseline, &cli.write_baseline) {
+        new_baseline
+            .save()
+            .with_context(|| format!("Failed to write baseline {}", path.display()))?;
+        eprintln!(
+            "Wrote {} problem(s) to baseline {}",
+            new_baseline.len(),
+            path.display()
+        );
+        if has_errors {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Baseline entries without a matching problem can be dropped
+    if let Some(baseline) = &baseline {
+        let stale = baseline.stale_entries();
+        if !stale.is_empty() {
+            eprintln!(
+                "{} stale baseline entry(s) no longer match a problem, \
+                 remove them with --write-baseline:",
+                stale.len()
+            );
+            for entry in &stale {
+                eprintln!(
+                    "  {}: {}, {} problem(s)",
+                    entry.path, entry.rule, entry.count
+                );
+            }
+        }
+    }
+
     // Print summary if there were problems
     if total_problems > 0 {
         eprintln!();
@@ -152,21 +477,30 @@ fn main() -> Result<()> {
 
 /// Run in fix mode
 #[allow(clippy::collapsible_if)] // Nested ifs required for MSRV 1.85 compatibility
-fn run_fix_mode(cli: &Cli, config: &Config, yaml_files: &[PathBuf]) -> Result<()> {
-    let registry = config.create_registry();
-    let fixer = Fixer::new(&registry);
-
+fn run_fix_mode(cli: &Cli, linters: &mut Linters, yaml_files: &[PathBuf]) -> Result<()> {
     let mut total_fixed = 0;
     let mut files_fixed = 0;
     let mut files_with_unfixable = 0;
+    let mut has_diff = false;
 
     let is_dry_run = cli.dry_run;
+    let use_colors = should_use_colors(&cli.color);
+
+    // In dry-run mode stdout only carries the diff, so it can be piped to `patch`
+    let report = |message: String| {
+        if is_dry_run {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    };
 
     for file in yaml_files {
         let content = fs::read_to_string(file)
             .with_context(|| format!("Failed to read {}", file.display()))?;
 
-        let result = fixer.fix(&file.display().to_string(), &content);
+        let registry = linters.for_file(file)?.registry_for_path(file)?;
+        let result = Fixer::new(&registry).fix(&file.display().to_string(), &content);
 
         if result.has_fixes() {
             files_fixed += 1;
@@ -180,17 +514,27 @@ fn run_fix_mode(cli: &Cli, config: &Config, yaml_files: &[PathBuf]) -> Result<()
                 .collect();
 
             let action = if is_dry_run { "would fix" } else { "fixed" };
-            println!(
+            report(format!(
                 "{}: {} {} issue(s) ({})",
                 file.display(),
                 action,
                 result.fixes_applied,
                 fix_summary.join(", ")
-            );
-
-            // Write fixed content (only in non-dry-run mode)
-            if !is_dry_run {
-                if let Some(fixed_content) = &result.fixed_content {
+            ));
+
+            if let Some(fixed_content) = &result.fixed_content {
+                if is_dry_run {
+                    // Show the changes instead of writing them
+                    let diff = unified_diff(&file.display().to_string(), &content, fixed_content);
+                    if !diff.is_empty() {
+                        has_diff = true;
+                        if use_colors {
+                            print!("{}", colorize_diff(&diff));
+                        } else {
+                            print!("{}", diff);
+                        }
+                    }
+                } else {
                     fs::write(file, fixed_content)
                         .with_context(|| format!("Failed to write {}", file.display()))?;
                 }
@@ -203,72 +547,287 @@ fn run_fix_mode(cli: &Cli, config: &Config, yaml_files: &[PathBuf]) -> Result<()
     }
 
     // Print summary
-    println!();
+    report(String::new());
     let summary_action = if is_dry_run { "Would fix" } else { "Fixed" };
-    println!(
+    report(format!(
         "{} {} issue(s) in {} file(s)",
         summary_action, total_fixed, files_fixed
-    );
+    ));
 
     if files_with_unfixable > 0 {
-        println!(
+        report(format!(
             "{} file(s) have unfixable issues (run without --fix to see details)",
             files_with_unfixable
-        );
+        ));
+        std::process::exit(1);
+    }
+
+    // A pending diff fails dry-run so it can gate CI
+    if has_diff {
         std::process::exit(1);
     }
 
     Ok(())
 }
 
+/// Write a `.yamllint` in the current directory matching the files found in
+/// `paths`
+fn run_init(cli: &Cli, paths: &[PathBuf], force: bool) -> Result<()> {
+    let config_path = Path::new(CONFIG_FILE_NAMES[0]);
+    if config_path.exists() && !force {
+        return Err(anyhow::anyhow!(
+            "{} already exists, use --force to overwrite it",
+            config_path.display()
+        ));
+    }
+
+    // Existing configs don't decide which files are scanned
+    let mut linters = Linters::Fixed {
+        linter: Arc::new(Linter::with_defaults()),
+        source: String::new(),
+    };
+    let files = walk_yaml_files(cli, paths, &mut linters, true)?;
+    if files.is_empty() {
+        return Err(anyhow::anyhow!("No YAML files found"));
+    }
+
+    let contents = files
+        .iter()
+        .map(|file| {
+            fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let yaml = starter_config(&contents)?;
+
+    fs::write(config_path, &yaml)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    print!("{}", yaml);
+    eprintln!(
+        "Wrote {} from {} file(s)",
+        config_path.display(),
+        files.len()
+    );
+    Ok(())
+}
+
+/// Run `config check`, reporting how `file` or the config used for the
+/// current directory differs from yamllint
+fn run_config_check(cli: &Cli, file: Option<&Path>) -> Result<()> {
+    let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+    let config_path = file
+        .or(cli.config.as_deref())
+        .map(Path::to_path_buf)
+        .or_else(|| Config::find_config_file(&current_dir))
+        .or_else(Config::global_config_file)
+        .ok_or_else(|| anyhow::anyhow!("No config file found"))?;
+
+    let config = Config::load_from_file(&config_path)
+        .with_context(|| format!("Failed to load config from {}", config_path.display()))?;
+    print!("{}", compat_report(&config_path, &config));
+
+    if !config.compat_notes.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// The `config check` report for `config`, loaded from `path`
+fn compat_report(path: &Path, config: &Config) -> String {
+    let notes = &config.compat_notes;
+    if notes.is_empty() {
+        return format!("{}: all keys are supported\n", path.display());
+    }
+
+    let count = |kind: CompatKind| notes.iter().filter(|note| note.kind == kind).count();
+    let mut report = format!(
+        "{}: {} unsupported, {} behaving differently\n",
+        path.display(),
+        count(CompatKind::Unsupported),
+        count(CompatKind::Different)
+    );
+    for note in notes {
+        report.push_str(&format!("  {}\n", note));
+    }
+    report
+}
+
+/// Linters to use for the linted files
+enum Linters {
+    /// One config given on the command line, used for every file
+    Fixed { linter: Arc<Linter>, source: String },
+    /// The nearest config files of each file, or `fallback` without any
+    Discovered {
+        resolver: ConfigResolver,
+        fallback_source: String,
+    },
+}
+
+impl Linters {
+    /// Linter configured for `file`
+    fn for_file(&mut self, file: &Path) -> Result<Arc<Linter>> {
+        match self {
+            Linters::Fixed { linter, .. } => Ok(Arc::clone(linter)),
+            Linters::Discovered { resolver, .. } => resolver
+                .linter_for(file)
+                .with_context(|| format!("Failed to load config for {}", file.display())),
+        }
+    }
+
+    /// Config files discovered for `file`, outermost first
+    ///
+    /// Empty when `file` uses a config given on the command line or the
+    /// fallback config.
+    fn config_files_for(&mut self, file: &Path) -> Result<Vec<PathBuf>> {
+        match self {
+            Linters::Fixed { .. } => Ok(Vec::new()),
+            Linters::Discovered { resolver, .. } => resolver
+                .config_files_for(file)
+                .with_context(|| format!("Failed to load config for {}", file.display())),
+        }
+    }
+
+    /// Describe where the config for `file` comes from
+    fn config_source_for(&mut self, file: &Path) -> Result<String> {
+        let files = self.config_files_for(file)?;
+        match self {
+            Linters::Fixed { source, .. } => Ok(source.clone()),
+            Linters::Discovered {
+                fallback_source, ..
+            } if files.is_empty() => Ok(fallback_source.clone()),
+            Linters::Discovered { .. } => {
+                let files: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+                Ok(files.join(" + "))
+            }
+        }
+    }
+}
+
 /// Load configuration from CLI options
-fn load_config(cli: &Cli) -> Result<Config> {
-    if let Some(config_path) = &cli.config {
-        // Load from specified path
+///
+/// Precedence, highest first:
+/// 1. `--config`, then `--preset`, for every file
+/// 2. Config files in the file's directory and its parents
+/// 3. A config file found from the current directory upwards
+/// 4. The file named by `YAMLLINT_CONFIG_FILE`
+/// 5. The user config, `$XDG_CONFIG_HOME/yamllint/config` or
+///    `~/.config/yamllint/config`
+/// 6. The default preset
+fn load_config(cli: &Cli) -> Result<Linters> {
+    let load = |config_path: &Path| {
         Config::load_from_file(config_path)
             .with_context(|| format!("Failed to load config from {}", config_path.display()))
+    };
+
+    if let Some(config_path) = &cli.config {
+        // Load from specified path
+        Ok(Linters::Fixed {
+            linter: Arc::new(Linter::new(load(config_path)?)?),
+            source: config_path.display().to_string(),
+        })
     } else if let Some(preset) = &cli.preset {
         // Use specified preset
-        match preset.as_str() {
-            "default" => Ok(Config::with_default_preset()),
-            "relaxed" => Ok(Config::with_relaxed_preset()),
-            _ => Err(anyhow::anyhow!("Unknown preset: {}", preset)),
-        }
+        let config = match preset.as_str() {
+            "default" => Config::with_default_preset(),
+            "relaxed" => Config::with_relaxed_preset(),
+            _ => return Err(anyhow::anyhow!("Unknown preset: {}", preset)),
+        };
+        Ok(Linters::Fixed {
+            linter: Arc::new(Linter::new(config)?),
+            source: format!("preset {}", preset),
+        })
     } else {
-        // Try to find config file in current directory and parents
+        // Files without a config file of their own fall back to the one
+        // found from the current directory, then to the global configs
         let current_dir = std::env::current_dir().context("Failed to get current directory")?;
-        if let Some(config_path) = Config::find_config_file(&current_dir) {
-            Config::load_from_file(&config_path)
-                .with_context(|| format!("Failed to load config from {}", config_path.display()))
-        } else {
-            // Use default preset
-            Ok(Config::with_default_preset())
-        }
+        let fallback_path =
+            Config::find_config_file(&current_dir).or_else(Config::global_config_file);
+
+        let (fallback, fallback_source) = match fallback_path {
+            Some(config_path) => (load(&config_path)?, config_path.display().to_string()),
+            None => (
+                Config::with_default_preset(),
+                "preset default (no config file found)".to_string(),
+            ),
+        };
+        Ok(Linters::Discovered {
+            resolver: ConfigResolver::new(fallback)?,
+            fallback_source,
+        })
     }
 }
 
-/// Collect all YAML files from the given paths
-fn collect_yaml_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+/// Collect the files to lint
+///
+/// Files given explicitly are linted unless `ignore` in their config matches
+/// them; files found by walking a directory are linted if they also match
+/// `yaml-files`. Unless `--no-ignore` or `--hidden` is given, the walk skips
+/// files excluded by ignore files and hidden files. Symlinks are followed,
+/// and symlink cycles are reported and skipped.
+fn collect_yaml_files(cli: &Cli, linters: &mut Linters) -> Result<Vec<PathBuf>> {
+    let yaml_files = find_yaml_files(cli, linters, true)?;
+
+    // Files named explicitly are only left out when the config ignores them,
+    // e.g. when a pre-commit hook passes ignored files
+    if yaml_files.is_empty() && !cli.paths.iter().any(|path| path.is_file()) {
+        return Err(anyhow::anyhow!("No YAML files found"));
+    }
+
+    Ok(yaml_files)
+}
+
+/// Find the files to lint as described on [`collect_yaml_files`], printing
+/// warnings about unreadable paths if `warn` is set
+fn find_yaml_files(cli: &Cli, linters: &mut Linters, warn: bool) -> Result<Vec<PathBuf>> {
+    walk_yaml_files(cli, &cli.paths, linters, warn)
+}
+
+/// Find the YAML files among `paths` and in the directories among them, as
+/// described on [`collect_yaml_files`]
+fn walk_yaml_files(
+    cli: &Cli,
+    paths: &[PathBuf],
+    linters: &mut Linters,
+    warn: bool,
+) -> Result<Vec<PathBuf>> {
     let mut yaml_files = Vec::new();
 
     for path in paths {
         if path.is_file() {
-            if is_yaml_file(path) {
+            if !linters.for_file(path)?.config().is_ignored(path) {
                 yaml_files.push(path.clone());
             }
         } else if path.is_dir() {
             // Walk directory and collect YAML files
-            for entry in WalkDir::new(path)
+            let walk = WalkBuilder::new(path)
                 .follow_links(true)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
+                .standard_filters(!cli.no_ignore)
+                .hidden(!cli.hidden)
+                // Respect .gitignore outside git checkouts too, e.g. in
+                // unpacked source archives
+                .require_git(false)
+                .build();
+
+            for entry in walk {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        if warn {
+                            eprintln!("Warning: {}", e);
+                        }
+                        continue;
+                    }
+                };
                 let entry_path = entry.path();
-                if entry_path.is_file() && is_yaml_file(entry_path) {
+                if !entry_path.is_file() {
+                    continue;
+                }
+                let linter = linters.for_file(entry_path)?;
+                let config = linter.config();
+                if config.is_yaml_file(entry_path) && !config.is_ignored(entry_path) {
                     yaml_files.push(entry_path.to_path_buf());
                 }
             }
-        } else {
+        } else if warn {
             eprintln!(
                 "Warning: {} is neither a file nor a directory",
                 path.display()
@@ -276,27 +835,9 @@ fn collect_yaml_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
         }
     }
 
-    if yaml_files.is_empty() {
-        return Err(anyhow::anyhow!("No YAML files found"));
-    }
-
     Ok(yaml_files)
 }
 
-/// Check if a file is a YAML file based on extension
-fn is_yaml_file(path: &std::path::Path) -> bool {
-    if let Some(ext) = path.extension() {
-        matches!(ext.to_str(), Some("yaml") | Some("yml"))
-    } else {
-        // Also check for common config file names without extensions
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            matches!(name, ".yamllint" | ".yamllint.yml" | ".yamllint.yaml")
-        } else {
-            false
-        }
-    }
-}
-
 /// Configure color output based on mode and environment
 fn configure_colors(mode: &ColorMode) {
     colored::control::set_override(should_use_colors(mode));
@@ -317,12 +858,152 @@ fn should_use_colors(mode: &ColorMode) -> bool {
 mod tests {
     use super::*;
 
+    /// Create a fresh scratch directory for a test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("yaml-lint-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn collect(args: &[&str], dir: &Path) -> Vec<PathBuf> {
+        let mut argv = vec!["yaml-lint"];
+        argv.extend_from_slice(args);
+        let cli = Cli::parse_from(
+            argv.into_iter()
+                .map(String::from)
+                .chain([dir.display().to_string()]),
+        );
+        let mut linters = Linters::Fixed {
+            linter: Arc::new(Linter::with_defaults()),
+            source: String::new(),
+        };
+
+        let mut files: Vec<PathBuf> = collect_yaml_files(&cli, &mut linters)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.strip_prefix(dir).unwrap().to_path_buf())
+            .collect();
+        files.sort();
+        files
+    }
+
     #[test]
-    fn test_is_yaml_file() {
-        assert!(is_yaml_file(std::path::Path::new("test.yaml")));
-        assert!(is_yaml_file(std::path::Path::new("test.yml")));
-        assert!(is_yaml_file(std::path::Path::new(".yamllint")));
-        assert!(!is_yaml_file(std::path::Path::new("test.txt")));
-        assert!(!is_yaml_file(std::path::Path::new("test")));
+    fn test_collect_yaml_files_respects_ignore_files() {
+        let dir = scratch_dir("walk");
+        for file in [
+            "app.yaml",
+            "node_modules/dep.yaml",
+            ".github/ci.yml",
+            "notes.txt",
+        ] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "key: value\n").unwrap();
+        }
+        fs::write(dir.join(".gitignore"), "node_modules/\n").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&dir, dir.join("node_modules/loop")).unwrap();
+
+        assert_eq!(collect(&[], &dir), [PathBuf::from("app.yaml")]);
+        assert_eq!(
+            collect(&["--hidden"], &dir),
+            [PathBuf::from(".github/ci.yml"), PathBuf::from("app.yaml")]
+        );
+        assert_eq!(
+            collect(&["--no-ignore"], &dir),
+            [
+                PathBuf::from("app.yaml"),
+                PathBuf::from("node_modules/dep.yaml")
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_collect_yaml_files_skips_config_ignore() {
+        let dir = scratch_dir("config-ignore");
+        for file in ["app.yaml", "generated/out.yaml"] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "key: value\n").unwrap();
+        }
+        fs::write(dir.join(".yamllint"), "ignore: [generated/]\n").unwrap();
+
+        // Ignored files are skipped even when named explicitly
+        let generated = dir.join("generated/out.yaml");
+        let cli = Cli::parse_from([
+            "yaml-lint".to_string(),
+            generated.display().to_string(),
+            dir.display().to_string(),
+        ]);
+        let mut linters = Linters::Fixed {
+            linter: Arc::new(
+                Linter::new(Config::load_from_file(&dir.join(".yamllint")).unwrap()).unwrap(),
+            ),
+            source: String::new(),
+        };
+        assert_eq!(
+            collect_yaml_files(&cli, &mut linters).unwrap(),
+            [dir.join("app.yaml")]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_paths_named_like_subcommands_are_linted() {
+        let dir = scratch_dir("subcommand-paths");
+        fs::create_dir_all(dir.join("config")).unwrap();
+        fs::write(dir.join("rules"), "key: value\n").unwrap();
+
+        for (args, paths) in [
+            (&["yaml-lint", "config"][..], &["config"][..]),
+            (
+                &[
+                    "yaml-lint",
+                    "-c",
+                    ".yamllint",
+                    "--strict",
+                    "config",
+                    "rules",
+                ],
+                &["config", "rules"],
+            ),
+            (&["yaml-lint", "rules", "-f", "parsable"], &["rules"]),
+        ] {
+            let cli = parse_cli(args, &dir);
+            assert!(cli.command.is_none(), "{:?}", args);
+            assert_eq!(
+                cli.paths,
+                paths.iter().map(PathBuf::from).collect::<Vec<_>>()
+            );
+        }
+
+        // Arguments that are not paths still go to the subcommand
+        let cli = parse_cli(["yaml-lint", "config", "check"], &dir);
+        assert!(matches!(cli.command, Some(Command::Config { .. })));
+        let cli = parse_cli(["yaml-lint", "lsp"], &dir);
+        assert!(matches!(cli.command, Some(Command::Lsp)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compat_report() {
+        let path = Path::new(".yamllint");
+        let config = Config::load_from_str("rules:\n  truthy: warning\n").unwrap();
+        assert_eq!(
+            compat_report(path, &config),
+            ".yamllint: all keys are supported\n"
+        );
+
+        let config = Config::load_from_str("rules:\n  anchors: enable\n").unwrap();
+        assert_eq!(
+            compat_report(path, &config),
+            ".yamllint: 1 unsupported, 0 behaving differently\n  \
+             `rules.anchors` (unsupported): this yamllint rule is not implemented and does not run\n"
+        );
     }
 }
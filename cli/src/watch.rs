@@ -0,0 +1,391 @@
+//! Watch mode: re-lint files as they change
+
+use crate::{Cli, Linters, find_yaml_files, load_config};
+use anyhow::{Context, Result};
+use notify_debouncer_full::notify::{EventKind, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{Debouncer, RecommendedCache, new_debouncer};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+use yaml_lint_core::LintProblem;
+use yaml_lint_core::baseline::fingerprint;
+use yaml_lint_core::config::CONFIG_FILE_NAMES;
+use yaml_lint_core::output::OutputFormatter;
+
+/// How long files must stay unchanged before they are linted, so that a
+/// burst of writes, e.g. an editor saving several files, is linted once
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Escape sequence clearing the terminal and moving the cursor home
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+type Watcher = Debouncer<RecommendedWatcher, RecommendedCache>;
+
+/// Identity of a problem, to tell new problems from ones already reported
+///
+/// Like baseline entries, problems are keyed by a fingerprint of their
+/// line's content rather than by line number, so inserting a line doesn't
+/// report every problem below it again.
+type ProblemKey = (String, String, String);
+
+/// Lint `yaml_files`, then keep watching the paths given on the command line
+/// and report the problems that appear or disappear as files change
+///
+/// Changes are reported by the OS. Only the changed files are linted again,
+/// except when a config file changes: then the configuration is reloaded
+/// and every file is re-linted.
+pub fn run(
+    cli: &Cli,
+    mut linters: Linters,
+    mut yaml_files: Vec<PathBuf>,
+    formatter: &dyn OutputFormatter,
+) -> Result<()> {
+    let mut reported: HashMap<PathBuf, Vec<ProblemKey>> = HashMap::new();
+
+    lint_and_report(&mut linters, &yaml_files, &mut reported, formatter);
+    print_status(&reported);
+
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer =
+        new_debouncer(DEBOUNCE, None, sender).context("Failed to watch files for changes")?;
+    let mut watches = Watches::new(cli, &mut debouncer)?;
+    watches.watch_config_dirs(cli, &mut linters, &yaml_files, &mut debouncer);
+
+    for events in receiver {
+        let events = match events {
+            Ok(events) => events,
+            Err(errors) => {
+                for e in errors {
+                    eprintln!("Error watching files: {}", e);
+                }
+                continue;
+            }
+        };
+        // Reading a file to lint it is an access too
+        let changed: BTreeSet<PathBuf> = events
+            .into_iter()
+            .filter(|event| !matches!(event.kind, EventKind::Access(_)))
+            .flat_map(|event| event.event.paths)
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+
+        let config_changed = changed.iter().any(|path| is_config_file(cli, path));
+        if config_changed {
+            // Keep the previous config while a config file is invalid, e.g.
+            // half-way through editing it
+            match load_config(cli) {
+                Ok(reloaded) => linters = reloaded,
+                Err(e) => eprintln!("Error reloading config: {:#}", e),
+            }
+        }
+
+        // Files may have been created or deleted, or the config may have
+        // changed which files are linted
+        let linted: HashMap<PathBuf, &PathBuf> = yaml_files
+            .iter()
+            .map(|file| (canonical_path(file), file))
+            .collect();
+        let rewalk = config_changed
+            || changed
+                .iter()
+                .any(|path| !linted.contains_key(path) || !path.exists());
+        let to_lint: Vec<PathBuf> = if rewalk {
+            let Ok(files) = find_yaml_files(cli, &mut linters, false) else {
+                continue;
+            };
+            yaml_files = files;
+            yaml_files
+                .iter()
+                .filter(|file| config_changed || changed.contains(&canonical_path(file)))
+                .cloned()
+                .collect()
+        } else {
+            changed
+                .iter()
+                .filter_map(|path| linted.get(path).map(|file| (*file).clone()))
+                .collect()
+        };
+
+        let linted: HashSet<&PathBuf> = yaml_files.iter().collect();
+        let removed: Vec<PathBuf> = reported
+            .keys()
+            .filter(|file| !linted.contains(file))
+            .cloned()
+            .collect();
+
+        // Changes to files that aren't linted, e.g. in `.git`, print nothing
+        if !config_changed && to_lint.is_empty() && removed.is_empty() {
+            continue;
+        }
+
+        if cli.clear {
+            print!("{}", CLEAR_SCREEN);
+        }
+        if config_changed {
+            eprintln!("Config changed, re-linting all files");
+            // The new config may apply other config files
+            watches.watch_config_dirs(cli, &mut linters, &yaml_files, &mut debouncer);
+        }
+
+        // Files no longer linted have no problems left
+        for file in removed {
+            if reported.remove(&file).is_some_and(|keys| !keys.is_empty()) {
+                println!("{}: no longer linted", file.display());
+            }
+        }
+
+        lint_and_report(&mut linters, &to_lint, &mut reported, formatter);
+        print_status(&reported);
+    }
+
+    Ok(())
+}
+
+/// Directories watched for changes
+struct Watches {
+    /// Canonical paths given on the command line, watched recursively
+    roots: Vec<PathBuf>,
+    /// Directories of config files outside the roots, watched on their own
+    config_dirs: BTreeSet<PathBuf>,
+}
+
+impl Watches {
+    /// Start watching the paths given on the command line
+    fn new(cli: &Cli, debouncer: &mut Watcher) -> Result<Self> {
+        let mut roots = Vec::new();
+
+        for path in &cli.paths {
+            let path = canonical_path(path);
+            // A file is watched through its directory, as editors often
+            // save by replacing the file
+            let (dir, mode) = if path.is_dir() {
+                (path.clone(), RecursiveMode::Recursive)
+            } else {
+                let dir = path.parent().unwrap_or(&path).to_path_buf();
+                (dir, RecursiveMode::NonRecursive)
+            };
+            debouncer
+                .watch(&dir, mode)
+                .with_context(|| format!("Failed to watch {}", dir.display()))?;
+            roots.push(path);
+        }
+
+        Ok(Self {
+            roots,
+            config_dirs: BTreeSet::new(),
+        })
+    }
+
+    /// Watch the directories of the config files in effect that lie outside
+    /// the watched paths, and stop watching ones no longer in effect
+    fn watch_config_dirs(
+        &mut self,
+        cli: &Cli,
+        linters: &mut Linters,
+        yaml_files: &[PathBuf],
+        debouncer: &mut Watcher,
+    ) {
+        let mut config_files: BTreeSet<PathBuf> = cli.config.iter().cloned().collect();
+        for file in yaml_files {
+            if let Ok(files) = linters.config_files_for(file) {
+                config_files.extend(files);
+            }
+        }
+
+        let dirs: BTreeSet<PathBuf> = config_files
+            .iter()
+            .filter_map(|file| canonical_path(file).parent().map(Path::to_path_buf))
+            .filter(|dir| !self.roots.iter().any(|root| dir.starts_with(root)))
+            .collect();
+
+        for dir in self.config_dirs.difference(&dirs) {
+            let _ = debouncer.unwatch(dir);
+        }
+        for dir in dirs.difference(&self.config_dirs) {
+            if let Err(e) = debouncer.watch(dir, RecursiveMode::NonRecursive) {
+                eprintln!("Warning: failed to watch {}: {}", dir.display(), e);
+            }
+        }
+        self.config_dirs = dirs;
+    }
+}
+
+/// Lint `files` and print the problems that are new since they were last
+/// reported, along with the number of problems that went away
+fn lint_and_report(
+    linters: &mut Linters,
+    files: &[PathBuf],
+    reported: &mut HashMap<PathBuf, Vec<ProblemKey>>,
+    formatter: &dyn OutputFormatter,
+) {
+    for file in files {
+        let result = linters.for_file(file).and_then(|linter| {
+            let content = fs::read_to_string(file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            Ok((linter.lint_content(file, &content)?, content))
+        });
+
+        let (problems, content) = match result {
+            Ok(linted) => linted,
+            Err(e) => {
+                eprintln!("Error linting {}: {:#}", file.display(), e);
+                continue;
+            }
+        };
+
+        let keys = problem_keys(&problems, &content);
+        let previous = reported.remove(file).unwrap_or_default();
+        let (added, fixed) = delta(&previous, &problems, &keys);
+
+        if !added.is_empty() {
+            print!(
+                "{}",
+                formatter.format_problems_with_source(
+                    &added,
+                    &file.display().to_string(),
+                    &content
+                )
+            );
+        }
+        if fixed > 0 {
+            println!("{}: {} problem(s) fixed", file.display(), fixed);
+        }
+
+        reported.insert(file.clone(), keys);
+    }
+}
+
+/// Problems of `current` not in `previous`, and the number of problems of
+/// `previous` not in `current`
+///
+/// Each previous problem matches one current problem at most, so a copy of
+/// a line with a problem reports the problem again.
+fn delta(
+    previous: &[ProblemKey],
+    current: &[LintProblem],
+    current_keys: &[ProblemKey],
+) -> (Vec<LintProblem>, usize) {
+    let mut remaining: HashMap<&ProblemKey, usize> = HashMap::new();
+    for key in previous {
+        *remaining.entry(key).or_default() += 1;
+    }
+
+    let mut added = Vec::new();
+    for (problem, key) in current.iter().zip(current_keys) {
+        match remaining.get_mut(key) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => added.push(problem.clone()),
+        }
+    }
+
+    (added, remaining.values().sum())
+}
+
+/// Keys of `problems` found in `content`
+fn problem_keys(problems: &[LintProblem], content: &str) -> Vec<ProblemKey> {
+    let lines: Vec<&str> = content.lines().collect();
+
+    problems
+        .iter()
+        .map(|problem| {
+            let line = lines
+                .get(problem.line.wrapping_sub(1))
+                .copied()
+                .unwrap_or("");
+            (
+                problem.rule.clone(),
+                fingerprint(line),
+                problem.message.clone(),
+            )
+        })
+        .collect()
+}
+
+/// Print the number of problems left after a round of linting
+fn print_status(reported: &HashMap<PathBuf, Vec<ProblemKey>>) {
+    let total: usize = reported.values().map(Vec::len).sum();
+    let files = reported.values().filter(|p| !p.is_empty()).count();
+    eprintln!(
+        "{} problem(s) in {} file(s), watching for changes...",
+        total, files
+    );
+}
+
+/// Check whether a change to `path` affects the configuration
+///
+/// The config given with `--config` is compared by canonical path, since
+/// events and editors name it differently from the command line, e.g.
+/// absolute rather than relative.
+pub fn is_config_file(cli: &Cli, path: &Path) -> bool {
+    cli.config
+        .as_deref()
+        .is_some_and(|config| canonical_path(config) == canonical_path(path))
+        || path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| CONFIG_FILE_NAMES.contains(&name))
+}
+
+/// Canonical form of `path`, or its absolute form if it doesn't exist,
+/// e.g. after being deleted
+pub fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use yaml_lint_core::LintLevel;
+
+    fn problem(line: usize, rule: &str) -> LintProblem {
+        LintProblem::new(line, 1, "message", rule, LintLevel::Error)
+    }
+
+    #[test]
+    fn test_delta() {
+        let previous = [problem(1, "colons"), problem(2, "truthy")];
+        let previous = problem_keys(&previous, "a:  1\nb: yes\n");
+
+        // A line inserted above moves the truthy problem without changing it
+        let content = "# comment\na: 1\nb: yes\nc:  2\n";
+        let current = [problem(3, "truthy"), problem(4, "colons")];
+        let (added, fixed) = delta(&previous, &current, &problem_keys(&current, content));
+        assert_eq!(added, [problem(4, "colons")]);
+        assert_eq!(fixed, 1);
+
+        let keys = problem_keys(&current, content);
+        let (added, fixed) = delta(&keys, &current, &keys);
+        assert!(added.is_empty());
+        assert_eq!(fixed, 0);
+
+        // A copy of a line reports its problem again
+        let content = "b: yes\nb: yes\n";
+        let current = [problem(1, "truthy"), problem(2, "truthy")];
+        let (added, fixed) = delta(&previous, &current, &problem_keys(&current, content));
+        assert_eq!(added, [problem(2, "truthy")]);
+        assert_eq!(fixed, 1);
+    }
+
+    #[test]
+    fn test_is_config_file_compares_canonical_paths() {
+        let dir = std::env::temp_dir().join(format!("yaml-lint-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("lint.yaml"), "extends: default\n").unwrap();
+
+        let config = dir.join("sub/../lint.yaml");
+        let cli = Cli::parse_from(["yaml-lint", "-c", config.to_str().unwrap(), "--watch", "."]);
+        assert!(is_config_file(&cli, &dir.join("lint.yaml")));
+        assert!(is_config_file(&cli, &dir.join("sub/.yamllint")));
+        assert!(!is_config_file(&cli, &dir.join("sub/lint.yaml")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,362 @@
+//! Baselines of accepted problems
+//!
+//! A baseline records the problems present when a rule is adopted, so that
+//! only problems introduced later are reported. Problems are keyed by file,
+//! rule and a fingerprint of the content of their line rather than by line
+//! number, so edits elsewhere in a file don't invalidate the baseline.
+
+use crate::Result;
+use crate::problem::LintProblem;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Version of the baseline file format
+const FORMAT_VERSION: u32 = 1;
+
+/// Problems accepted at some point, loaded from or saved to a baseline file
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    path: PathBuf,
+    /// Directory the paths of the entries are relative to
+    base_dir: PathBuf,
+    /// Number of accepted problems for each key
+    entries: BTreeMap<EntryKey, usize>,
+    /// Accepted problems not matched by a linted problem yet
+    remaining: HashMap<EntryKey, usize>,
+    /// Keyed paths of the files checked against the baseline
+    checked: HashSet<String>,
+}
+
+/// Problems of one rule on lines with the same content in one file
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Path of the file, relative to the directory of the baseline file
+    pub path: String,
+    /// Name of the rule reporting the problems
+    pub rule: String,
+    /// Fingerprint of the content of the problems' line
+    pub fingerprint: String,
+    /// Number of problems
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub count: usize,
+}
+
+type EntryKey = (String, String, String);
+
+/// On-disk layout of a baseline file
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    #[serde(default)]
+    problems: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Create an empty baseline to be saved at `path`
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            base_dir: base_dir_of(path),
+            entries: BTreeMap::new(),
+            remaining: HashMap::new(),
+            checked: HashSet::new(),
+        }
+    }
+
+    /// Load the baseline file at `path`
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let file: BaselineFile = serde_yaml::from_str(&content)
+            .map_err(|e| crate::LintError::BaselineError(format!("{}: {}", path.display(), e)))?;
+
+        if file.version != FORMAT_VERSION {
+            return Err(crate::LintError::BaselineError(format!(
+                "{}: unsupported version {}, expected {}",
+                path.display(),
+                file.version,
+                FORMAT_VERSION
+            )));
+        }
+
+        let mut baseline = Self::new(path);
+        for entry in file.problems {
+            *baseline
+                .entries
+                .entry((entry.path, entry.rule, entry.fingerprint))
+                .or_default() += entry.count;
+        }
+        baseline.remaining = baseline
+            .entries
+            .iter()
+            .map(|(key, count)| (key.clone(), *count))
+            .collect();
+
+        Ok(baseline)
+    }
+
+    /// Write the baseline to its file
+    pub fn save(&self) -> Result<()> {
+        let file = BaselineFile {
+            version: FORMAT_VERSION,
+            problems: self.entries().collect(),
+        };
+        let yaml = serde_yaml::to_string(&file)
+            .map_err(|e| crate::LintError::BaselineError(e.to_string()))?;
+
+        std::fs::write(
+            &self.path,
+            format!(
+                "# yaml-lint baseline, regenerate with --write-baseline\n{}",
+                yaml
+            ),
+        )?;
+        Ok(())
+    }
+
+    /// Accept `problems` found in `content` of the file at `path`
+    pub fn add(&mut self, path: &Path, content: &str, problems: &[LintProblem]) {
+        let path = self.key_path(path);
+        let lines: Vec<&str> = content.lines().collect();
+
+        for problem in problems {
+            let key = (
+                path.clone(),
+                problem.rule.clone(),
+                line_fingerprint(&lines, problem.line),
+            );
+            *self.remaining.entry(key.clone()).or_default() += 1;
+            *self.entries.entry(key).or_default() += 1;
+        }
+    }
+
+    /// Split `problems` found in `content` of the file at `path` into new
+    /// problems and problems accepted by the baseline, in that order
+    ///
+    /// Each accepted problem matches one problem at most, so a file with
+    /// more problems on identical lines than recorded reports the extra ones.
+    pub fn filter(
+        &mut self,
+        path: &Path,
+        content: &str,
+        problems: Vec<LintProblem>,
+    ) -> (Vec<LintProblem>, Vec<LintProblem>) {
+        let path = self.key_path(path);
+        let lines: Vec<&str> = content.lines().collect();
+        self.checked.insert(path.clone());
+
+        problems.into_iter().partition(|problem| {
+            let key = (
+                path.clone(),
+                problem.rule.clone(),
+                line_fingerprint(&lines, problem.line),
+            );
+            match self.remaining.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        })
+    }
+
+    /// Entries that no longer match a problem, so the baseline can shrink
+    ///
+    /// Only covers files checked with [`Baseline::filter`] and files that no
+    /// longer exist; `count` is the number of unmatched problems.
+    pub fn stale_entries(&self) -> Vec<BaselineEntry> {
+        let mut stale: Vec<BaselineEntry> = self
+            .remaining
+            .iter()
+            .filter(|((path, _, _), count)| {
+                **count > 0 && (self.checked.contains(path) || !self.base_dir.join(path).exists())
+            })
+            .map(|((path, rule, fingerprint), count)| BaselineEntry {
+                path: path.clone(),
+                rule: rule.clone(),
+                fingerprint: fingerprint.clone(),
+                count: *count,
+            })
+            .collect();
+        stale.sort();
+        stale
+    }
+
+    /// Accepted problems, sorted by path and rule
+    pub fn entries(&self) -> impl Iterator<Item = BaselineEntry> + '_ {
+        self.entries
+            .iter()
+            .map(|((path, rule, fingerprint), count)| BaselineEntry {
+                path: path.clone(),
+                rule: rule.clone(),
+                fingerprint: fingerprint.clone(),
+                count: *count,
+            })
+    }
+
+    /// Number of accepted problems
+    pub fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    /// Check whether the baseline accepts no problems
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Path of `path` relative to the baseline's directory, with `/`
+    /// separators, so the baseline works from any working directory
+    fn key_path(&self, path: &Path) -> String {
+        let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let relative = absolute.strip_prefix(&self.base_dir).unwrap_or(path);
+
+        relative
+            .components()
+            .filter(|component| !matches!(component, std::path::Component::CurDir))
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Fingerprint of the content of a line, ignoring surrounding whitespace
+///
+/// Uses 64-bit FNV-1a, which unlike `DefaultHasher` is stable across Rust
+/// releases.
+pub fn fingerprint(line: &str) -> String {
+    let hash = line
+        .trim()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// Fingerprint of `line` (1-based) of `lines`, empty past the end
+fn line_fingerprint(lines: &[&str], line: usize) -> String {
+    fingerprint(lines.get(line.wrapping_sub(1)).copied().unwrap_or(""))
+}
+
+/// Canonical directory containing the baseline file at `path`
+fn base_dir_of(path: &Path) -> PathBuf {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())
+}
+
+fn one() -> usize {
+    1
+}
+
+fn is_one(count: &usize) -> bool {
+    *count == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LintLevel;
+
+    fn problem(line: usize, rule: &str) -> LintProblem {
+        LintProblem::new(line, 1, "message", rule, LintLevel::Error)
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_surrounding_whitespace() {
+        assert_eq!(fingerprint("  key: value"), fingerprint("key: value  "));
+        assert_ne!(fingerprint("key: value"), fingerprint("key: other"));
+        assert_eq!(fingerprint(""), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn test_filter_matches_moved_lines() {
+        let path = Path::new("baseline-test-does-not-exist.yaml");
+        let mut baseline = Baseline::new(Path::new("baseline.yaml"));
+        baseline.add(path, "a: 1\nb:  2\n", &[problem(2, "colons")]);
+
+        // The line moved down and a new problem appeared
+        let (new, known) = baseline.filter(
+            path,
+            "x: 0\na: 1\nb:  2\nc:  3\n",
+            vec![problem(3, "colons"), problem(4, "colons")],
+        );
+        assert_eq!(new.iter().map(|p| p.line).collect::<Vec<_>>(), [4]);
+        assert_eq!(known.iter().map(|p| p.line).collect::<Vec<_>>(), [3]);
+        assert!(baseline.stale_entries().is_empty());
+    }
+
+    #[test]
+    fn test_filter_counts_identical_lines() {
+        let path = Path::new("baseline-test-does-not-exist.yaml");
+        let mut baseline = Baseline::new(Path::new("baseline.yaml"));
+        baseline.add(
+            path,
+            "- yes\n- yes\n",
+            &[problem(1, "truthy"), problem(2, "truthy")],
+        );
+
+        let (new, _) = baseline.filter(
+            path,
+            "- yes\n- yes\n- yes\n",
+            vec![
+                problem(1, "truthy"),
+                problem(2, "truthy"),
+                problem(3, "truthy"),
+            ],
+        );
+        assert_eq!(new.len(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("yaml-lint-baseline-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("k8s")).unwrap();
+        let file = dir.join("k8s/app.yaml");
+        std::fs::write(&file, "- yes\n- yes\n").unwrap();
+        let path = dir.join("baseline.yaml");
+
+        let mut baseline = Baseline::new(&path);
+        baseline.add(
+            &file,
+            "- yes\n- yes\n",
+            &[problem(1, "truthy"), problem(2, "truthy")],
+        );
+        baseline.save().unwrap();
+
+        let loaded = Baseline::load(&path).unwrap();
+        let entries: Vec<BaselineEntry> = loaded.entries().collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "k8s/app.yaml");
+        assert_eq!(entries[0].count, 2);
+        assert_eq!(loaded.len(), 2);
+
+        std::fs::write(&path, "version: 2\n").unwrap();
+        assert!(Baseline::load(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_stale_entries() {
+        let path = Path::new("baseline-test-does-not-exist.yaml");
+        let mut baseline = Baseline::new(Path::new("baseline.yaml"));
+        baseline.add(
+            path,
+            "a:  1\nb:  2\n",
+            &[problem(1, "colons"), problem(2, "colons")],
+        );
+
+        let (new, _) = baseline.filter(path, "a:  1\nb: 2\n", vec![problem(1, "colons")]);
+        assert!(new.is_empty());
+
+        let stale = baseline.stale_entries();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].path, "baseline-test-does-not-exist.yaml");
+        assert_eq!(stale[0].rule, "colons");
+        assert_eq!(stale[0].fingerprint, fingerprint("b:  2"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Compatibility with Python yamllint config files
+//!
+//! Config files written for yamllint load unchanged: keys and rules this
+//! project does not implement are accepted, and each one that changes
+//! behaviour is recorded as a [`CompatNote`] on the loaded config, so
+//! `yaml-lint config check` can report what a migration loses. Keys yamllint
+//! does not know either are still errors.
+
+use std::fmt;
+
+/// yamllint rules this project does not implement
+pub const YAMLLINT_ONLY_RULES: [&str; 12] = [
+    "anchors",
+    "braces",
+    "brackets",
+    "commas",
+    "comments-indentation",
+    "document-end",
+    "empty-values",
+    "float-values",
+    "key-ordering",
+    "new-lines",
+    "octal-values",
+    "quoted-strings",
+];
+
+/// yamllint rule options this project does not implement, with the value
+/// matching what the rule here does
+///
+/// Setting one of them to that value changes nothing, so it is accepted
+/// silently.
+const UNSUPPORTED_OPTIONS: [(&str, &str, bool); 3] = [
+    ("line-length", "allow-non-breakable-words", false),
+    ("line-length", "allow-non-breakable-inline-mappings", false),
+    ("key-duplicates", "forbid-duplicated-merge-keys", false),
+];
+
+/// How far a config key is from its yamllint behaviour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompatKind {
+    /// The key is accepted but has no effect
+    Unsupported,
+    /// The key works, but not quite as in yamllint
+    Different,
+}
+
+impl fmt::Display for CompatKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CompatKind::Unsupported => "unsupported",
+            CompatKind::Different => "different",
+        })
+    }
+}
+
+/// A yamllint config key that does not behave as it does in yamllint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatNote {
+    /// YAML path of the key, e.g. `rules.line-length.allow-non-breakable-words`
+    pub path: String,
+    pub kind: CompatKind,
+    /// What happens instead
+    pub message: String,
+}
+
+impl CompatNote {
+    pub(crate) fn unsupported(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            kind: CompatKind::Unsupported,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn different(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            kind: CompatKind::Different,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CompatNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` ({}): {}", self.path, self.kind, self.message)
+    }
+}
+
+/// Unsupported yamllint options of `rule`, with the value matching this
+/// project's behaviour
+pub(crate) fn unsupported_options(rule: &str) -> impl Iterator<Item = (&'static str, bool)> {
+    UNSUPPORTED_OPTIONS
+        .iter()
+        .filter(move |(name, _, _)| *name == rule)
+        .map(|(_, option, value)| (*option, *value))
+}
+
+/// Options whose default differs from yamllint's, noted when a rule
+/// configured with a mapping, as in a yamllint config, leaves them unset
+pub(crate) fn default_differences(
+    rule: &str,
+    map: &serde_yaml::Mapping,
+    path: &str,
+) -> Vec<CompatNote> {
+    let unset = |option: &str| !map.contains_key(option);
+    let mut notes = Vec::new();
+
+    match rule {
+        "truthy" if unset("check-keys") => notes.push(CompatNote::different(
+            format!("{}.check-keys", path),
+            "defaults to false here but to true in yamllint; set it to check keys",
+        )),
+        "document-start" if unset("present") => notes.push(CompatNote::different(
+            format!("{}.present", path),
+            "is unset, so the rule checks nothing; yamllint defaults to true",
+        )),
+        "line-length" if unset("allow-non-breakable-words") => notes.push(CompatNote::different(
+            format!("{}.allow-non-breakable-words", path),
+            "yamllint defaults to true and allows long lines without spaces, \
+             such as URLs; this project reports them",
+        )),
+        _ => {}
+    }
+
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BUILTIN_RULES;
+
+    #[test]
+    fn test_rule_tables() {
+        for rule in YAMLLINT_ONLY_RULES {
+            assert!(!BUILTIN_RULES.contains(&rule), "{}", rule);
+        }
+        for (rule, _, _) in UNSUPPORTED_OPTIONS {
+            assert!(BUILTIN_RULES.contains(&rule), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn test_default_differences() {
+        let map: serde_yaml::Mapping = serde_yaml::from_str("level: warning").unwrap();
+        let notes = default_differences("truthy", &map, "rules.truthy");
+        assert_eq!(notes.len(), 1);
+        assert_eq!(
+            notes[0].to_string(),
+            "`rules.truthy.check-keys` (different): defaults to false here but to true \
+             in yamllint; set it to check keys"
+        );
+
+        let map: serde_yaml::Mapping = serde_yaml::from_str("check-keys: false").unwrap();
+        assert!(default_differences("truthy", &map, "rules.truthy").is_empty());
+        assert!(default_differences("colons", &map, "rules.colons").is_empty());
+    }
+}
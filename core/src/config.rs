@@ -1,9 +1,13 @@
 //! Configuration system for the linter
 
 use crate::Result;
+use crate::compat::{self, CompatNote};
 use crate::rules::RuleLevel;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use indexmap::IndexMap;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
 /// Configuration for a single rule
 #[derive(Debug, Clone, PartialEq)]
@@ -43,6 +47,8 @@ pub enum RuleOptions {
     },
     Indentation {
         spaces: IndentConfig,
+        indent_sequences: IndentSequencesConfig,
+        check_multi_line_strings: bool,
     },
     Colons {
         max_spaces_before: usize,
@@ -64,9 +70,11 @@ pub enum RuleOptions {
     Truthy {
         allowed_values: Vec<String>,
         check_keys: bool,
+        fix_style: Option<TruthyFixStyleConfig>,
     },
     DocumentStart {
         present: DocumentStartConfig,
+        insert_after_comments: bool,
     },
 }
 
@@ -79,6 +87,28 @@ pub enum IndentConfig {
     Consistent,
 }
 
+/// Block sequence indentation configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentSequencesConfig {
+    /// Sequences must be indented under their parent key (`true`)
+    Indented,
+    /// Sequences must not be indented (`false`)
+    NotIndented,
+    /// Either style is accepted (`whatever`)
+    Whatever,
+    /// All sequences must follow the style of the first one (`consistent`)
+    Consistent,
+}
+
+/// Truthy auto-fix configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TruthyFixStyleConfig {
+    /// Rewrite to the first allowed value of the same polarity (`canonical`)
+    Canonical,
+    /// Quote the value as a string (`quote`)
+    Quote,
+}
+
 /// Document start configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DocumentStartConfig {
@@ -90,13 +120,285 @@ pub enum DocumentStartConfig {
     Disabled,
 }
 
+/// File names recognised as config files, in order of preference
+pub const CONFIG_FILE_NAMES: [&str; 3] = [".yamllint", ".yamllint.yml", ".yamllint.yaml"];
+
+/// Names of the rules that can be configured
+pub const BUILTIN_RULES: [&str; 11] = [
+    "trailing-spaces",
+    "line-length",
+    "document-start",
+    "colons",
+    "key-duplicates",
+    "indentation",
+    "new-line-at-end-of-file",
+    "empty-lines",
+    "hyphens",
+    "comments",
+    "truthy",
+];
+
+/// Environment variable naming a config file used when no project config
+/// file applies
+pub const CONFIG_FILE_ENV: &str = "YAMLLINT_CONFIG_FILE";
+
+/// Default `yaml-files` patterns
+pub const DEFAULT_YAML_FILES: [&str; 3] = ["*.yaml", "*.yml", ".yamllint"];
+
+/// `yaml-files` matcher used by configs that do not set it
+static DEFAULT_MATCHER: LazyLock<YamlFiles> = LazyLock::new(YamlFiles::default);
+
+/// Compiled glob patterns from a config
+///
+/// Patterns containing a `/` match paths relative to the linted root;
+/// other patterns match the file name in any directory.
+#[derive(Debug, Clone)]
+struct GlobMatcher {
+    set: GlobSet,
+    /// Whether each glob in `set` excludes the paths it matches
+    negated: Vec<bool>,
+    /// Directory the globs are relative to, for configs loaded from a file
+    base_dir: Option<PathBuf>,
+}
+
+impl GlobMatcher {
+    /// Compile `patterns`, naming the config `key` they come from in errors
+    fn new(patterns: &[String], key: &str) -> Result<Self> {
+        let globs = patterns
+            .iter()
+            .map(|pattern| (pattern.as_str(), Self::normalize(pattern), false))
+            .collect();
+        Self::build(globs, key)
+    }
+
+    /// Compile `.gitignore`-style `patterns`
+    ///
+    /// Blank lines and `#` comments are skipped, a pattern matching a
+    /// directory matches everything in it, and `!` re-includes paths
+    /// matched by earlier patterns.
+    fn gitignore(patterns: &[String], key: &str) -> Result<Self> {
+        let mut globs = Vec::new();
+        for pattern in patterns {
+            let line = pattern.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            let glob = Self::normalize(line.strip_suffix('/').unwrap_or(line));
+            globs.push((pattern.as_str(), format!("{}/**", glob), negated));
+            globs.push((pattern.as_str(), glob, negated));
+        }
+        Self::build(globs, key)
+    }
+
+    /// Glob matching `pattern` relative to the linted root
+    fn normalize(pattern: &str) -> String {
+        match pattern.strip_prefix('/') {
+            Some(anchored) => anchored.to_string(),
+            None if !pattern.contains('/') => format!("**/{}", pattern),
+            None => pattern.trim_start_matches("./").to_string(),
+        }
+    }
+
+    /// Compile `(pattern, glob, negated)` triples
+    fn build(globs: Vec<(&str, String, bool)>, key: &str) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        let mut negated = Vec::new();
+        for (pattern, glob, negate) in globs {
+            let glob = GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| {
+                    crate::LintError::ConfigError(format!(
+                        "Invalid {} glob '{}': {}",
+                        key, pattern, e
+                    ))
+                })?;
+            builder.add(glob);
+            negated.push(negate);
+        }
+
+        let set = builder
+            .build()
+            .map_err(|e| crate::LintError::ConfigError(format!("Invalid {} globs: {}", key, e)))?;
+
+        Ok(Self {
+            set,
+            negated,
+            base_dir: None,
+        })
+    }
+
+    /// Check whether `path` matches any of the patterns
+    ///
+    /// For configs loaded from a file, `path` is matched relative to the
+    /// directory of that file and never matches files outside it.
+    fn is_match(&self, path: &Path) -> bool {
+        let relative = match &self.base_dir {
+            Some(base_dir) => {
+                let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+                match absolute.strip_prefix(base_dir) {
+                    Ok(relative) => relative.to_path_buf(),
+                    Err(_) => return false,
+                }
+            }
+            None => path.to_path_buf(),
+        };
+
+        // `./k8s/app.yaml` should match `k8s/**`
+        let relative: PathBuf = relative
+            .components()
+            .filter(|component| !matches!(component, std::path::Component::CurDir))
+            .collect();
+
+        // The last matching pattern decides
+        self.set
+            .matches(relative)
+            .into_iter()
+            .max()
+            .is_some_and(|index| !self.negated[index])
+    }
+}
+
+/// Rule configurations applied to files matching a set of globs
+#[derive(Debug, Clone)]
+pub struct ConfigOverride {
+    /// Glob patterns selecting the files this override applies to
+    pub files: Vec<String>,
+    /// Rule configurations replacing the base ones for matching files
+    pub rules: IndexMap<String, RuleConfig>,
+    matcher: GlobMatcher,
+    /// Whether this override comes from a rule's yamllint `ignore` patterns
+    /// rather than from `overrides`
+    from_rule_ignore: bool,
+    rule_mappings: RuleMappings,
+}
+
+impl ConfigOverride {
+    /// Create an override for the given glob patterns
+    ///
+    /// Patterns containing a `/` match paths relative to the linted root;
+    /// other patterns match the file name in any directory.
+    pub fn new(files: Vec<String>, rules: IndexMap<String, RuleConfig>) -> Result<Self> {
+        let matcher = GlobMatcher::new(&files, "overrides")?;
+        Ok(Self {
+            files,
+            rules,
+            matcher,
+            from_rule_ignore: false,
+            rule_mappings: RuleMappings::new(),
+        })
+    }
+
+    /// Override disabling `rule` for files matching the `.gitignore`-style
+    /// `patterns` of the rule's `ignore` key, as in yamllint
+    fn rule_ignore(rule: &str, patterns: Vec<String>) -> Result<Self> {
+        let matcher = GlobMatcher::gitignore(&patterns, "ignore")?;
+        let mut rules = IndexMap::new();
+        rules.insert(rule.to_string(), RuleConfig::Level(RuleLevel::Disable));
+        Ok(Self {
+            files: patterns,
+            rules,
+            matcher,
+            from_rule_ignore: true,
+            rule_mappings: RuleMappings::new(),
+        })
+    }
+
+    /// Check whether this override applies to `path`
+    ///
+    /// For configs loaded from a file, `path` is matched relative to the
+    /// directory of that file and never matches files outside it.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.matcher.is_match(path)
+    }
+}
+
+/// Glob patterns selecting the files linted when walking a directory
+///
+/// Matching follows the same rules as `overrides` globs. Files named
+/// explicitly on the command line are linted whatever their name.
+#[derive(Debug, Clone)]
+pub struct YamlFiles {
+    patterns: Vec<String>,
+    matcher: GlobMatcher,
+}
+
+impl YamlFiles {
+    /// Create a file selection from glob patterns
+    pub fn new(patterns: Vec<String>) -> Result<Self> {
+        let matcher = GlobMatcher::new(&patterns, "yaml-files")?;
+        Ok(Self { patterns, matcher })
+    }
+
+    /// The glob patterns, as configured
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Check whether `path` is selected
+    pub fn matches(&self, path: &Path) -> bool {
+        self.matcher.is_match(path)
+    }
+}
+
+impl Default for YamlFiles {
+    fn default() -> Self {
+        Self::new(DEFAULT_YAML_FILES.iter().map(|p| p.to_string()).collect())
+            .expect("default yaml-files globs are valid")
+    }
+}
+
+/// `.gitignore`-style patterns selecting files that are not linted
+///
+/// Patterns are matched like `overrides` globs, except that a pattern
+/// matching a directory matches everything in it and patterns starting with
+/// `!` re-include files matched by earlier ones. Blank lines and `#`
+/// comments are skipped.
+#[derive(Debug, Clone)]
+pub struct IgnorePatterns {
+    patterns: Vec<String>,
+    matcher: GlobMatcher,
+}
+
+impl IgnorePatterns {
+    /// Compile the given patterns
+    pub fn new(patterns: Vec<String>) -> Result<Self> {
+        let matcher = GlobMatcher::gitignore(&patterns, "ignore")?;
+        Ok(Self { patterns, matcher })
+    }
+
+    /// The patterns, as configured
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Check whether `path` is ignored
+    pub fn matches(&self, path: &Path) -> bool {
+        self.matcher.is_match(path)
+    }
+}
+
 /// Main configuration structure
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Rule configurations
     pub rules: IndexMap<String, RuleConfig>,
-    /// File patterns to ignore
-    pub ignore: Vec<String>,
+    /// Files that are not linted, even when named on the command line
+    pub ignore: Option<IgnorePatterns>,
+    /// Path-scoped rule configurations, later entries winning
+    pub overrides: Vec<ConfigOverride>,
+    /// Files linted when walking directories, `None` for the defaults
+    pub yaml_files: Option<YamlFiles>,
+    /// Whether config discovery stops at this config instead of layering it
+    /// on top of configs from parent directories
+    pub root: bool,
+    /// yamllint keys that are accepted but do not behave as in yamllint
+    pub compat_notes: Vec<CompatNote>,
+    rule_mappings: RuleMappings,
 }
 
 impl Config {
@@ -104,7 +406,12 @@ impl Config {
     pub fn new() -> Self {
         Self {
             rules: IndexMap::new(),
-            ignore: Vec::new(),
+            ignore: None,
+            overrides: Vec::new(),
+            yaml_files: None,
+            root: false,
+            compat_notes: Vec::new(),
+            rule_mappings: RuleMappings::new(),
         }
     }
 
@@ -165,330 +472,1369 @@ impl Config {
     }
 
     /// Load config from a YAML file
+    ///
+    /// Relative paths in `extends` and in `ignore`, `overrides` and
+    /// `yaml-files` patterns are resolved against the directory of `path`.
     pub fn load_from_file(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
-        Self::load_from_str(&content)
+        let yaml = Self::resolve_file(path, &mut Vec::new())?;
+        let mut config = Self::from_yaml(&yaml)?;
+
+        let base_dir = path
+            .canonicalize()
+            .ok()
+            .and_then(|file| file.parent().map(Path::to_path_buf));
+        for entry in &mut config.overrides {
+            entry.matcher.base_dir = base_dir.clone();
+        }
+        if let Some(ignore) = &mut config.ignore {
+            ignore.matcher.base_dir = base_dir.clone();
+        }
+        if let Some(yaml_files) = &mut config.yaml_files {
+            yaml_files.matcher.base_dir = base_dir;
+        }
+
+        Ok(config)
     }
 
     /// Parse rule-specific options based on rule name
-    fn parse_rule_options(rule_name: &str, map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        match rule_name {
-            "line-length" => Self::parse_line_length_options(map),
-            "indentation" => Self::parse_indentation_options(map),
-            "colons" => Self::parse_colons_options(map),
-            "empty-lines" => Self::parse_empty_lines_options(map),
-            "hyphens" => Self::parse_hyphens_options(map),
-            "comments" => Self::parse_comments_options(map),
-            "truthy" => Self::parse_truthy_options(map),
-            "document-start" => Self::parse_document_start_options(map),
-            _ => Err(crate::LintError::ConfigError(format!(
-                "Rule '{}' does not support options",
-                rule_name
-            ))),
-        }
+    ///
+    /// `path` is the YAML path of the rule's mapping, used in errors. Returns
+    /// `None` for rules without options, whose mapping may only set `level`.
+    fn parse_rule_options(
+        rule_name: &str,
+        map: &serde_yaml::Mapping,
+        path: &str,
+    ) -> Result<Option<RuleOptions>> {
+        let options = |known: &'static [&'static str]| OptionMap::new(map, path, known);
+
+        let parsed = match rule_name {
+            "line-length" => Self::parse_line_length_options(&options(&["level", "max"])?)?,
+            "indentation" => Self::parse_indentation_options(&options(&[
+                "level",
+                "spaces",
+                "indent-sequences",
+                "check-multi-line-strings",
+            ])?)?,
+            "colons" => Self::parse_colons_options(&options(&[
+                "level",
+                "max-spaces-before",
+                "max-spaces-after",
+            ])?)?,
+            "empty-lines" => Self::parse_empty_lines_options(&options(&[
+                "level",
+                "max",
+                "max-start",
+                "max-end",
+            ])?)?,
+            "hyphens" => Self::parse_hyphens_options(&options(&["level", "max-spaces-after"])?)?,
+            "comments" => Self::parse_comments_options(&options(&[
+                "level",
+                "require-starting-space",
+                "ignore-shebangs",
+                "min-spaces-from-content",
+            ])?)?,
+            "truthy" => Self::parse_truthy_options(&options(&[
+                "level",
+                "allowed-values",
+                "check-keys",
+                "fix-style",
+            ])?)?,
+            "document-start" => Self::parse_document_start_options(&options(&[
+                "level",
+                "present",
+                "insert-after-comments",
+            ])?)?,
+            _ => {
+                if let Some(key) = map.keys().find(|key| key.as_str() != Some("level")) {
+                    return Err(crate::LintError::ConfigError(format!(
+                        "`{}.{}`: rule '{}' does not support options",
+                        path,
+                        key.as_str().unwrap_or("?"),
+                        rule_name
+                    )));
+                }
+                return Ok(None);
+            }
+        };
+
+        Ok(Some(parsed))
     }
 
     /// Parse line-length options
-    fn parse_line_length_options(map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        let max = map
-            .get(serde_yaml::Value::String("max".to_string()))
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(80);
+    fn parse_line_length_options(options: &OptionMap) -> Result<RuleOptions> {
+        let max = options.usize("max", 80)?;
 
         if max == 0 {
-            return Err(crate::LintError::ConfigError(
-                "line-length max must be greater than 0".to_string(),
-            ));
+            return Err(options.error("max", "must be greater than 0"));
         }
 
         Ok(RuleOptions::LineLength { max })
     }
 
     /// Parse indentation options
-    fn parse_indentation_options(map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        let spaces_value = map.get(serde_yaml::Value::String("spaces".to_string()));
-
-        let spaces = match spaces_value {
+    fn parse_indentation_options(options: &OptionMap) -> Result<RuleOptions> {
+        let spaces = match options.get("spaces") {
             Some(serde_yaml::Value::Number(n)) => {
-                let num = n.as_u64().ok_or_else(|| {
-                    crate::LintError::ConfigError(
-                        "indentation spaces must be a positive integer".to_string(),
-                    )
-                })?;
+                let num = n
+                    .as_u64()
+                    .ok_or_else(|| options.error("spaces", "must be a positive integer"))?;
 
                 if num == 0 || num > 16 {
-                    return Err(crate::LintError::ConfigError(
-                        "indentation spaces must be between 1 and 16".to_string(),
-                    ));
+                    return Err(options.error("spaces", "must be between 1 and 16"));
                 }
 
                 IndentConfig::Fixed(num as usize)
             }
             Some(serde_yaml::Value::String(s)) if s == "consistent" => IndentConfig::Consistent,
             None => IndentConfig::Consistent, // Default
-            _ => {
-                return Err(crate::LintError::ConfigError(
-                    "indentation spaces must be a number or 'consistent'".to_string(),
+            Some(value) => {
+                return Err(options.error(
+                    "spaces",
+                    &format!(
+                        "must be a number or 'consistent', found {}",
+                        describe(value)
+                    ),
                 ));
             }
         };
 
-        Ok(RuleOptions::Indentation { spaces })
+        let indent_sequences = match options.get("indent-sequences") {
+            Some(serde_yaml::Value::Bool(true)) | None => IndentSequencesConfig::Indented,
+            Some(serde_yaml::Value::Bool(false)) => IndentSequencesConfig::NotIndented,
+            Some(serde_yaml::Value::String(s)) if s == "whatever" => {
+                IndentSequencesConfig::Whatever
+            }
+            Some(serde_yaml::Value::String(s)) if s == "consistent" => {
+                IndentSequencesConfig::Consistent
+            }
+            Some(value) => {
+                return Err(options.error(
+                    "indent-sequences",
+                    &format!(
+                        "must be true, false, 'whatever' or 'consistent', found {}",
+                        describe(value)
+                    ),
+                ));
+            }
+        };
+
+        let check_multi_line_strings = options.bool("check-multi-line-strings", false)?;
+
+        Ok(RuleOptions::Indentation {
+            spaces,
+            indent_sequences,
+            check_multi_line_strings,
+        })
     }
 
     /// Parse colons options
-    fn parse_colons_options(map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        let max_spaces_before = map
-            .get(serde_yaml::Value::String("max-spaces-before".to_string()))
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(0);
-
-        let max_spaces_after = map
-            .get(serde_yaml::Value::String("max-spaces-after".to_string()))
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(1);
-
+    fn parse_colons_options(options: &OptionMap) -> Result<RuleOptions> {
         Ok(RuleOptions::Colons {
-            max_spaces_before,
-            max_spaces_after,
+            max_spaces_before: options.usize_or_off("max-spaces-before", 0, usize::MAX)?,
+            max_spaces_after: options.usize_or_off("max-spaces-after", 1, usize::MAX)?,
         })
     }
 
     /// Parse empty-lines options
-    fn parse_empty_lines_options(map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        let max = map
-            .get(serde_yaml::Value::String("max".to_string()))
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(2);
-
-        let max_start = map
-            .get(serde_yaml::Value::String("max-start".to_string()))
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(0);
-
-        let max_end = map
-            .get(serde_yaml::Value::String("max-end".to_string()))
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(0);
-
+    fn parse_empty_lines_options(options: &OptionMap) -> Result<RuleOptions> {
         Ok(RuleOptions::EmptyLines {
-            max,
-            max_start,
-            max_end,
+            max: options.usize("max", 2)?,
+            max_start: options.usize("max-start", 0)?,
+            max_end: options.usize("max-end", 0)?,
         })
     }
 
     /// Parse hyphens options
-    fn parse_hyphens_options(map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        let max_spaces_after = map
-            .get(serde_yaml::Value::String("max-spaces-after".to_string()))
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(1);
-
-        Ok(RuleOptions::Hyphens { max_spaces_after })
+    fn parse_hyphens_options(options: &OptionMap) -> Result<RuleOptions> {
+        Ok(RuleOptions::Hyphens {
+            max_spaces_after: options.usize_or_off("max-spaces-after", 1, usize::MAX)?,
+        })
     }
 
     /// Parse comments options
-    fn parse_comments_options(map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        let require_starting_space = map
-            .get(serde_yaml::Value::String(
-                "require-starting-space".to_string(),
-            ))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(true);
-
-        let ignore_shebangs = map
-            .get(serde_yaml::Value::String("ignore-shebangs".to_string()))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(true);
-
-        let min_spaces_from_content = map
-            .get(serde_yaml::Value::String(
-                "min-spaces-from-content".to_string(),
-            ))
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(2);
-
+    fn parse_comments_options(options: &OptionMap) -> Result<RuleOptions> {
         Ok(RuleOptions::Comments {
-            require_starting_space,
-            ignore_shebangs,
-            min_spaces_from_content,
+            require_starting_space: options.bool("require-starting-space", true)?,
+            ignore_shebangs: options.bool("ignore-shebangs", true)?,
+            min_spaces_from_content: options.usize_or_off("min-spaces-from-content", 2, 0)?,
         })
     }
 
     /// Parse truthy options
-    fn parse_truthy_options(map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        let allowed_values = map
-            .get(serde_yaml::Value::String("allowed-values".to_string()))
-            .and_then(|v| v.as_sequence())
-            .map(|seq| {
-                seq.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_else(|| vec!["true".to_string(), "false".to_string()]);
+    fn parse_truthy_options(options: &OptionMap) -> Result<RuleOptions> {
+        let allowed_values = match options.get("allowed-values") {
+            Some(serde_yaml::Value::Sequence(values)) => values
+                .iter()
+                .map(|value| {
+                    value.as_str().map(str::to_string).ok_or_else(|| {
+                        options.error(
+                            "allowed-values",
+                            &format!("must be a list of strings, found {}", describe(value)),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec!["true".to_string(), "false".to_string()],
+            Some(value) => {
+                return Err(options.error(
+                    "allowed-values",
+                    &format!("must be a list of strings, found {}", describe(value)),
+                ));
+            }
+        };
 
         if allowed_values.is_empty() {
-            return Err(crate::LintError::ConfigError(
-                "truthy allowed-values cannot be empty".to_string(),
-            ));
+            return Err(options.error("allowed-values", "cannot be empty"));
         }
 
-        let check_keys = map
-            .get(serde_yaml::Value::String("check-keys".to_string()))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let check_keys = options.bool("check-keys", false)?;
+
+        let fix_style = match options.get("fix-style") {
+            Some(serde_yaml::Value::String(s)) if s == "canonical" => {
+                Some(TruthyFixStyleConfig::Canonical)
+            }
+            Some(serde_yaml::Value::String(s)) if s == "quote" => Some(TruthyFixStyleConfig::Quote),
+            None => None,
+            Some(value) => {
+                return Err(options.error(
+                    "fix-style",
+                    &format!("must be 'canonical' or 'quote', found {}", describe(value)),
+                ));
+            }
+        };
 
         Ok(RuleOptions::Truthy {
             allowed_values,
             check_keys,
+            fix_style,
         })
     }
 
     /// Parse document-start options
-    fn parse_document_start_options(map: &serde_yaml::Mapping) -> Result<RuleOptions> {
-        let present_value = map.get(serde_yaml::Value::String("present".to_string()));
+    fn parse_document_start_options(options: &OptionMap) -> Result<RuleOptions> {
+        let present = match options.get("present") {
+            Some(serde_yaml::Value::Bool(true)) => DocumentStartConfig::Required,
+            Some(serde_yaml::Value::Bool(false)) => DocumentStartConfig::Forbidden,
+            None => DocumentStartConfig::Disabled,
+            Some(value) => {
+                return Err(options.error(
+                    "present",
+                    &format!(
+                        "must be a boolean (true or false), found {}",
+                        describe(value)
+                    ),
+                ));
+            }
+        };
+
+        Ok(RuleOptions::DocumentStart {
+            present,
+            insert_after_comments: options.bool("insert-after-comments", true)?,
+        })
+    }
+
+    /// Load config from YAML string
+    ///
+    /// Relative paths in `extends` and `ignore-from-file` are resolved
+    /// against the current directory.
+    pub fn load_from_str(content: &str) -> Result<Self> {
+        let mut yaml: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| crate::LintError::ConfigError(format!("Invalid YAML: {}", e)))?;
+
+        let parents = Self::take_extends(&mut yaml)?;
+        Self::read_ignore_files(&mut yaml, Path::new("."))?;
+        let yaml = Self::merge_parents(&parents, yaml, Path::new("."), &mut Vec::new())?;
+        Self::from_yaml(&yaml)
+    }
+
+    /// Read a config file and resolve its `extends` chain
+    ///
+    /// `chain` holds the files currently being resolved, outermost first, and
+    /// is used to detect cycles and to name the chain in errors.
+    fn resolve_file(path: &Path, chain: &mut Vec<PathBuf>) -> Result<serde_yaml::Value> {
+        let read_error = |e: std::io::Error| {
+            crate::LintError::ConfigError(format!(
+                "Failed to read config {}: {}{}",
+                path.display(),
+                e,
+                Self::chain_context(chain)
+            ))
+        };
+        let canonical = path.canonicalize().map_err(read_error)?;
+        let content = std::fs::read_to_string(&canonical).map_err(read_error)?;
+
+        if chain.contains(&canonical) {
+            let cycle: Vec<String> = chain
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(crate::LintError::ConfigError(format!(
+                "Circular extends: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        let base_dir = canonical
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        chain.push(canonical);
+
+        // Errors in this file itself name it and the files extending it
+        let in_file = |e: crate::LintError, chain: &[PathBuf]| match e {
+            crate::LintError::ConfigError(msg) => {
+                let files: Vec<String> = chain.iter().map(|p| p.display().to_string()).collect();
+                crate::LintError::ConfigError(format!("{} (in {})", msg, files.join(" -> ")))
+            }
+            e => e,
+        };
+        let own = serde_yaml::from_str(&content)
+            .map_err(|e| crate::LintError::ConfigError(format!("Invalid YAML: {}", e)))
+            .and_then(|mut yaml| {
+                let parents = Self::take_extends(&mut yaml)?;
+                Self::read_ignore_files(&mut yaml, &base_dir)?;
+                Self::from_yaml(&yaml)?;
+                Ok((parents, yaml))
+            })
+            .map_err(|e| in_file(e, chain));
+
+        let result =
+            own.and_then(|(parents, yaml)| Self::merge_parents(&parents, yaml, &base_dir, chain));
+        chain.pop();
+
+        result
+    }
+
+    /// Replace the `ignore-from-file` keys of `yaml`, at the top level and in
+    /// rules, with `ignore` lists of the lines of the files they name
+    ///
+    /// Relative file paths are resolved against `base_dir`.
+    fn read_ignore_files(yaml: &mut serde_yaml::Value, base_dir: &Path) -> Result<()> {
+        let Some(map) = yaml.as_mapping_mut() else {
+            return Ok(());
+        };
+        Self::read_ignore_file(map, "", base_dir)?;
+
+        if let Some(serde_yaml::Value::Mapping(rules)) = map.get_mut("rules") {
+            for (rule_name, rule) in rules.iter_mut() {
+                if let (Some(rule_name), serde_yaml::Value::Mapping(rule)) =
+                    (rule_name.as_str(), rule)
+                {
+                    Self::read_ignore_file(rule, &format!("rules.{}", rule_name), base_dir)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replace `ignore-from-file` in the mapping at the YAML path `path`, as
+    /// described on [`Self::read_ignore_files`]
+    fn read_ignore_file(map: &mut serde_yaml::Mapping, path: &str, base_dir: &Path) -> Result<()> {
+        let Some(files) = map.remove("ignore-from-file") else {
+            return Ok(());
+        };
+        let key_path = |key: &str| match path {
+            "" => key.to_string(),
+            path => format!("{}.{}", path, key),
+        };
+
+        if map.contains_key("ignore") {
+            return Err(crate::LintError::ConfigError(format!(
+                "`{}` and `{}` cannot be used together",
+                key_path("ignore"),
+                key_path("ignore-from-file")
+            )));
+        }
+        let files = match &files {
+            serde_yaml::Value::String(file) => vec![file.as_str()],
+            serde_yaml::Value::Sequence(files) if files.iter().all(|f| f.is_string()) => {
+                files.iter().filter_map(serde_yaml::Value::as_str).collect()
+            }
+            value => {
+                return Err(crate::LintError::ConfigError(format!(
+                    "`{}` must be a file path or a list of them, found {}",
+                    key_path("ignore-from-file"),
+                    describe(value)
+                )));
+            }
+        };
+
+        let mut patterns = Vec::new();
+        for file in files {
+            let content = std::fs::read_to_string(base_dir.join(file)).map_err(|e| {
+                crate::LintError::ConfigError(format!(
+                    "Failed to read `{}` file {}: {}",
+                    key_path("ignore-from-file"),
+                    file,
+                    e
+                ))
+            })?;
+            patterns.extend(content.lines().map(serde_yaml::Value::from));
+        }
+        map.insert("ignore".into(), serde_yaml::Value::Sequence(patterns));
+
+        Ok(())
+    }
+
+    /// Remove `extends` from `yaml`, returning the parents it names
+    fn take_extends(yaml: &mut serde_yaml::Value) -> Result<Vec<String>> {
+        let extends = match yaml.as_mapping_mut() {
+            Some(map) => map.remove("extends"),
+            None => None,
+        };
+
+        match extends {
+            None => Ok(Vec::new()),
+            Some(serde_yaml::Value::String(parent)) => Ok(vec![parent]),
+            Some(serde_yaml::Value::Sequence(parents)) => parents
+                .into_iter()
+                .map(|parent| match parent {
+                    serde_yaml::Value::String(parent) => Ok(parent),
+                    _ => Err(crate::LintError::ConfigError(
+                        "extends entries must be strings".to_string(),
+                    )),
+                })
+                .collect(),
+            Some(_) => Err(crate::LintError::ConfigError(
+                "extends must be a preset name, a file path or a list of them".to_string(),
+            )),
+        }
+    }
+
+    /// Merge `parents` (preset names or file paths relative to `base_dir`)
+    /// with `yaml`
+    ///
+    /// Parents are applied in order, so later parents override earlier ones
+    /// and `yaml` itself overrides all of them.
+    fn merge_parents(
+        parents: &[String],
+        yaml: serde_yaml::Value,
+        base_dir: &Path,
+        chain: &mut Vec<PathBuf>,
+    ) -> Result<serde_yaml::Value> {
+        if parents.is_empty() {
+            return Ok(yaml);
+        }
+
+        let mut merged = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        for parent in parents {
+            let mut parent_yaml = match Self::preset_yaml(parent) {
+                Some(preset) => preset,
+                None => Self::resolve_file(&base_dir.join(parent), chain)?,
+            };
+            // Only the extending file decides whether discovery stops there
+            if let Some(map) = parent_yaml.as_mapping_mut() {
+                map.remove("root");
+            }
+            Self::merge_yaml(&mut merged, parent_yaml);
+        }
+        Self::merge_yaml(&mut merged, yaml);
+
+        Ok(merged)
+    }
+
+    /// Deep-merge `child` into `parent`
+    ///
+    /// Rule configurations are merged key by key, so a child setting only
+    /// `max` keeps the parent's level and other options, and a plain level
+    /// string only changes the level of a parent mapping. Options given for a
+    /// rule the parent disables enable it again. Other keys are replaced.
+    fn merge_yaml(parent: &mut serde_yaml::Value, child: serde_yaml::Value) {
+        let (Some(parent_map), serde_yaml::Value::Mapping(child_map)) =
+            (parent.as_mapping_mut(), child)
+        else {
+            return;
+        };
+
+        for (key, child_value) in child_map {
+            let is_rules = key.as_str() == Some("rules");
+            let is_overrides = key.as_str() == Some("overrides");
+            match (parent_map.get_mut(&key), child_value) {
+                (Some(parent_value), child_value) if is_rules => {
+                    Self::merge_rules(parent_value, child_value);
+                }
+                // Child overrides come after the parent's, so they win
+                (
+                    Some(serde_yaml::Value::Sequence(parent_overrides)),
+                    serde_yaml::Value::Sequence(child_overrides),
+                ) if is_overrides => {
+                    parent_overrides.extend(child_overrides);
+                }
+                (_, child_value) => {
+                    parent_map.insert(key, child_value);
+                }
+            }
+        }
+    }
+
+    /// Merge the child `rules` mapping into the parent one
+    fn merge_rules(parent: &mut serde_yaml::Value, child: serde_yaml::Value) {
+        let serde_yaml::Value::Mapping(child_rules) = child else {
+            *parent = child;
+            return;
+        };
+        let Some(parent_rules) = parent.as_mapping_mut() else {
+            *parent = serde_yaml::Value::Mapping(child_rules);
+            return;
+        };
+
+        for (rule, child_config) in child_rules {
+            let parent_config = parent_rules.get(&rule).cloned().map(Self::expand_enable);
+            let merged = match (parent_config.as_ref(), Self::expand_enable(child_config)) {
+                (Some(serde_yaml::Value::Mapping(parent_options)), child_config) => {
+                    let mut options = parent_options.clone();
+                    match child_config {
+                        serde_yaml::Value::Mapping(child_options) => options.extend(child_options),
+                        level => {
+                            options.insert("level".into(), level);
+                        }
+                    }
+                    serde_yaml::Value::Mapping(options)
+                }
+                // Configuring a disabled rule enables it, as in yamllint
+                (
+                    Some(parent_level @ serde_yaml::Value::String(_)),
+                    serde_yaml::Value::Mapping(child_options),
+                ) if parent_level.as_str() != Some("disable") => {
+                    let mut options = serde_yaml::Mapping::new();
+                    options.insert("level".into(), parent_level.clone());
+                    options.extend(child_options);
+                    serde_yaml::Value::Mapping(options)
+                }
+                (_, child_config) => child_config,
+            };
+            parent_rules.insert(rule, merged);
+        }
+    }
+
+    /// Replace the yamllint `enable` shorthand with the empty mapping it
+    /// stands for, which keeps the options and level of the rule it extends
+    fn expand_enable(rule_config: serde_yaml::Value) -> serde_yaml::Value {
+        match rule_config.as_str() {
+            Some("enable") => serde_yaml::Value::Mapping(serde_yaml::Mapping::new()),
+            _ => rule_config,
+        }
+    }
+
+    /// Raw YAML for a built-in preset, or `None` if `name` is not a preset
+    fn preset_yaml(name: &str) -> Option<serde_yaml::Value> {
+        let preset = match name {
+            "default" => Self::with_default_preset(),
+            "relaxed" => Self::with_relaxed_preset(),
+            _ => return None,
+        };
+
+        let mut rules = serde_yaml::Mapping::new();
+        for (rule_name, rule_config) in &preset.rules {
+            rules.insert(
+                rule_name.as_str().into(),
+                level_name(rule_config.level()).into(),
+            );
+        }
+
+        let mut yaml = serde_yaml::Mapping::new();
+        yaml.insert("rules".into(), serde_yaml::Value::Mapping(rules));
+        Some(serde_yaml::Value::Mapping(yaml))
+    }
+
+    /// Describe the files being resolved, for error messages
+    fn chain_context(chain: &[PathBuf]) -> String {
+        if chain.is_empty() {
+            return String::new();
+        }
+
+        let files: Vec<String> = chain.iter().map(|p| p.display().to_string()).collect();
+        format!(" (extended from {})", files.join(" -> "))
+    }
+
+    /// Build a config from YAML whose `extends` has already been resolved
+    fn from_yaml(yaml: &serde_yaml::Value) -> Result<Self> {
+        let mut config = Self::new();
+
+        let empty = serde_yaml::Mapping::new();
+        let map = match yaml {
+            serde_yaml::Value::Mapping(map) => map,
+            // An empty file
+            serde_yaml::Value::Null => &empty,
+            value => {
+                return Err(crate::LintError::ConfigError(format!(
+                    "config must be a mapping, found {}",
+                    describe(value)
+                )));
+            }
+        };
+        let options = OptionMap::new(
+            map,
+            "",
+            &[
+                "rules",
+                "ignore",
+                "locale",
+                "overrides",
+                "root",
+                "yaml-files",
+            ],
+        )?;
+        let mut compat = RuleCompat::default();
+
+        // Parse rules
+        match options.get("rules") {
+            Some(serde_yaml::Value::Mapping(rules)) => {
+                (config.rules, config.rule_mappings) =
+                    Self::parse_rules(rules, "rules", &mut compat)?;
+            }
+            Some(serde_yaml::Value::Null) | None => {}
+            Some(value) => {
+                return Err(options.error(
+                    "rules",
+                    &format!("must be a mapping, found {}", describe(value)),
+                ));
+            }
+        }
+
+        // Parse ignore patterns
+        if let Some(value) = options.get("ignore") {
+            config.ignore = Some(IgnorePatterns::new(Self::parse_ignore(value, "ignore")?)?);
+        }
+
+        // yamllint only uses the locale to sort keys for key-ordering
+        match options.get("locale") {
+            Some(serde_yaml::Value::String(_)) => {
+                compat.notes.push(CompatNote::unsupported(
+                    "locale",
+                    "has no effect, since key-ordering is not implemented",
+                ));
+            }
+            None => {}
+            Some(value) => {
+                return Err(options.error(
+                    "locale",
+                    &format!("must be a string, found {}", describe(value)),
+                ));
+            }
+        }
+
+        config.root = options.bool("root", false)?;
+
+        if let Some(value) = options.get("yaml-files") {
+            config.yaml_files = Some(YamlFiles::new(Self::parse_globs(
+                &options,
+                "yaml-files",
+                value,
+            )?)?);
+        }
+
+        // Parse path-scoped overrides
+        match options.get("overrides") {
+            Some(serde_yaml::Value::Sequence(overrides)) => {
+                for (index, entry) in overrides.iter().enumerate() {
+                    let path = format!("overrides[{}]", index);
+                    config
+                        .overrides
+                        .push(Self::parse_override(entry, &path, &mut compat)?);
+                }
+            }
+            None => {}
+            Some(value) => {
+                return Err(options.error(
+                    "overrides",
+                    &format!("must be a list, found {}", describe(value)),
+                ));
+            }
+        }
+
+        // Rules' ignore patterns win over overrides
+        for (rule_name, patterns) in compat.ignores {
+            config
+                .overrides
+                .push(ConfigOverride::rule_ignore(&rule_name, patterns)?);
+        }
+        config.compat_notes = compat.notes;
+
+        Ok(config)
+    }
+
+    /// Parse a `rules` mapping found at the YAML path `path`
+    ///
+    /// yamllint rules and options this project does not implement are
+    /// skipped, and rules' `ignore` patterns are collected, into `compat`.
+    fn parse_rules(
+        rules: &serde_yaml::Mapping,
+        path: &str,
+        compat: &mut RuleCompat,
+    ) -> Result<(IndexMap<String, RuleConfig>, RuleMappings)> {
+        let mut parsed = IndexMap::new();
+        let mut mappings = RuleMappings::new();
+
+        for (key, value) in rules {
+            let rule_name = key.as_str().ok_or_else(|| {
+                crate::LintError::ConfigError(format!(
+                    "`{}`: rule names must be strings, found {}",
+                    path,
+                    describe(key)
+                ))
+            })?;
+            let rule_path = format!("{}.{}", path, rule_name);
+            let value = Self::expand_enable(value.clone());
+
+            if compat::YAMLLINT_ONLY_RULES.contains(&rule_name) {
+                let level = match &value {
+                    serde_yaml::Value::String(_) => Self::parse_level(&value, &rule_path)?,
+                    serde_yaml::Value::Mapping(map) => match map.get("level") {
+                        Some(level) => Self::parse_level(level, &format!("{}.level", rule_path))?,
+                        None => RuleLevel::Error,
+                    },
+                    _ => {
+                        return Err(crate::LintError::ConfigError(format!(
+                            "`{}` must be enable, disable or a mapping, found {}",
+                            rule_path,
+                            describe(&value)
+                        )));
+                    }
+                };
+                if level != RuleLevel::Disable {
+                    compat.notes.push(CompatNote::unsupported(
+                        rule_path,
+                        "this yamllint rule is not implemented and does not run",
+                    ));
+                }
+                continue;
+            }
+
+            if !BUILTIN_RULES.contains(&rule_name) {
+                let known: Vec<&str> = BUILTIN_RULES
+                    .iter()
+                    .chain(&compat::YAMLLINT_ONLY_RULES)
+                    .copied()
+                    .collect();
+                return Err(crate::LintError::ConfigError(format!(
+                    "unknown rule `{}`{}",
+                    rule_path,
+                    did_you_mean(rule_name, &known)
+                )));
+            }
+
+            let rule_config = match value {
+                // Simple string level: "error", "warning", "disable"
+                serde_yaml::Value::String(_) => {
+                    RuleConfig::Level(Self::parse_level(&value, &rule_path)?)
+                }
+                // Mapping with options
+                serde_yaml::Value::Mapping(mut map) => {
+                    // Explicit level if specified, otherwise default to Error
+                    let level_path = format!("{}.level", rule_path);
+                    let level = map
+                        .get("level")
+                        .map(|level| Self::parse_level(level, &level_path))
+                        .transpose()?
+                        .unwrap_or(RuleLevel::Error);
+
+                    if let Some(ignore) = map.remove("ignore") {
+                        let patterns =
+                            Self::parse_ignore(&ignore, &format!("{}.ignore", rule_path))?;
+                        compat.ignores.push((rule_name.to_string(), patterns));
+                    }
+                    if level != RuleLevel::Disable {
+                        compat
+                            .notes
+                            .extend(compat::default_differences(rule_name, &map, &rule_path));
+                    }
+                    Self::take_unsupported_options(rule_name, &mut map, &rule_path, compat)?;
+
+                    // Parse rule-specific options
+                    let rule_config = match Self::parse_rule_options(rule_name, &map, &rule_path)? {
+                        Some(options) => RuleConfig::Detailed { level, options },
+                        None => RuleConfig::Level(level),
+                    };
+                    mappings.insert(rule_name.to_string(), map);
+                    rule_config
+                }
+                _ => {
+                    return Err(crate::LintError::ConfigError(format!(
+                        "`{}` must be a level (error, warning or disable) or a mapping, found {}",
+                        rule_path,
+                        describe(&value)
+                    )));
+                }
+            };
+
+            parsed.insert(rule_name.to_string(), rule_config);
+        }
+
+        Ok((parsed, mappings))
+    }
+
+    /// Remove the yamllint options `rule_name` does not implement from `map`,
+    /// noting in `compat` those set to a value this project cannot honour
+    fn take_unsupported_options(
+        rule_name: &str,
+        map: &mut serde_yaml::Mapping,
+        path: &str,
+        compat: &mut RuleCompat,
+    ) -> Result<()> {
+        for (option, supported) in compat::unsupported_options(rule_name) {
+            let Some(value) = map.remove(option) else {
+                continue;
+            };
+            let option_path = format!("{}.{}", path, option);
+            match value.as_bool() {
+                Some(value) if value == supported => {}
+                Some(_) => compat.notes.push(CompatNote::unsupported(
+                    option_path,
+                    format!("only {} is implemented", supported),
+                )),
+                None => {
+                    return Err(crate::LintError::ConfigError(format!(
+                        "`{}` must be a boolean (true or false), found {}",
+                        option_path,
+                        describe(&value)
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse `ignore` patterns found at the YAML path `path`, given as a
+    /// list or as a block of lines as in `.gitignore`
+    fn parse_ignore(value: &serde_yaml::Value, path: &str) -> Result<Vec<String>> {
+        let error = |value: &serde_yaml::Value| {
+            crate::LintError::ConfigError(format!(
+                "`{}` must be a block of patterns or a list of them, found {}",
+                path,
+                describe(value)
+            ))
+        };
+
+        match value {
+            serde_yaml::Value::String(patterns) => {
+                Ok(patterns.lines().map(str::to_string).collect())
+            }
+            serde_yaml::Value::Sequence(patterns) => patterns
+                .iter()
+                .map(|pattern| {
+                    pattern
+                        .as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| error(pattern))
+                })
+                .collect(),
+            value => Err(error(value)),
+        }
+    }
+
+    /// Parse a rule level found at the YAML path `path`
+    fn parse_level(value: &serde_yaml::Value, path: &str) -> Result<RuleLevel> {
+        match value.as_str() {
+            Some("error") => Ok(RuleLevel::Error),
+            Some("warning") => Ok(RuleLevel::Warning),
+            Some("disable") => Ok(RuleLevel::Disable),
+            _ => Err(crate::LintError::ConfigError(format!(
+                "`{}` must be error, warning or disable, found {}",
+                path,
+                describe(value)
+            ))),
+        }
+    }
+
+    /// Parse a single `overrides` entry found at the YAML path `path`
+    fn parse_override(
+        entry: &serde_yaml::Value,
+        path: &str,
+        compat: &mut RuleCompat,
+    ) -> Result<ConfigOverride> {
+        let map = entry.as_mapping().ok_or_else(|| {
+            crate::LintError::ConfigError(format!(
+                "`{}` must be a mapping with files and rules, found {}",
+                path,
+                describe(entry)
+            ))
+        })?;
+        let options = OptionMap::new(map, path, &["files", "rules"])?;
+
+        let files = match options.get("files") {
+            Some(value) => Self::parse_globs(&options, "files", value)?,
+            None => return Err(options.error("files", "is required")),
+        };
+
+        let rules = match options.get("rules") {
+            Some(serde_yaml::Value::Mapping(rules)) => {
+                let ignores = compat.ignores.len();
+                let rules = Self::parse_rules(rules, &format!("{}.rules", path), compat)?;
+                if let Some((rule_name, _)) = compat.ignores.get(ignores) {
+                    return Err(crate::LintError::ConfigError(format!(
+                        "`{}.rules.{}.ignore` is not supported in overrides, narrow `files` instead",
+                        path, rule_name
+                    )));
+                }
+                rules
+            }
+            None => (IndexMap::new(), RuleMappings::new()),
+            Some(value) => {
+                return Err(options.error(
+                    "rules",
+                    &format!("must be a mapping, found {}", describe(value)),
+                ));
+            }
+        };
+
+        let (rules, rule_mappings) = rules;
+        let mut entry = ConfigOverride::new(files, rules)?;
+        entry.rule_mappings = rule_mappings;
+        Ok(entry)
+    }
+
+    /// Parse a glob or a list of globs found at `key` of `options`
+    fn parse_globs(
+        options: &OptionMap,
+        key: &str,
+        value: &serde_yaml::Value,
+    ) -> Result<Vec<String>> {
+        match value {
+            serde_yaml::Value::String(pattern) => Ok(vec![pattern.clone()]),
+            serde_yaml::Value::Sequence(patterns) => patterns
+                .iter()
+                .map(|pattern| {
+                    pattern.as_str().map(str::to_string).ok_or_else(|| {
+                        options.error(
+                            key,
+                            &format!("must be glob strings, found {}", describe(pattern)),
+                        )
+                    })
+                })
+                .collect(),
+            value => Err(options.error(
+                key,
+                &format!(
+                    "must be a glob or a list of globs, found {}",
+                    describe(value)
+                ),
+            )),
+        }
+    }
+
+    /// Find a config file starting from the given directory
+    pub fn find_config_file(start_dir: &Path) -> Option<std::path::PathBuf> {
+        let mut current = start_dir.to_path_buf();
+
+        loop {
+            if let Some(config_path) = Self::config_file_in(&current) {
+                return Some(config_path);
+            }
+
+            // Move up one directory
+            if !current.pop() {
+                break;
+            }
+        }
+
+        None
+    }
+
+    /// Config file used when no project config file applies
+    ///
+    /// This is the file named by `YAMLLINT_CONFIG_FILE` if set, or else the
+    /// user config `$XDG_CONFIG_HOME/yamllint/config` (defaulting to
+    /// `~/.config/yamllint/config`) if it exists.
+    pub fn global_config_file() -> Option<PathBuf> {
+        Self::global_config_file_from(|name| std::env::var_os(name))
+    }
+
+    /// `global_config_file` reading environment variables through `var`
+    fn global_config_file_from(
+        var: impl Fn(&str) -> Option<std::ffi::OsString>,
+    ) -> Option<PathBuf> {
+        if let Some(path) = var(CONFIG_FILE_ENV).filter(|path| !path.is_empty()) {
+            return Some(PathBuf::from(path));
+        }
+
+        let config_home = var("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+        let path = config_home.join("yamllint").join("config");
+        path.is_file().then_some(path)
+    }
+
+    /// Config file directly in `dir`, if any
+    pub fn config_file_in(dir: &Path) -> Option<PathBuf> {
+        CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    /// Layer this config on top of `parent`, the config of an enclosing
+    /// directory
+    ///
+    /// Rules are combined as with `overrides`, `ignore` is replaced when this
+    /// config sets it, and the overrides of both apply, ours last.
+    pub(crate) fn layered_on(&self, parent: &Config) -> Config {
+        let mut config = parent.clone();
+        if config.rules.is_empty() && !self.rules.is_empty() {
+            config.rules = Self::with_default_preset().rules;
+        }
+        Self::apply_rules(&mut config.rules, &self.rules, &self.rule_mappings);
+        for (rule_name, map) in &self.rule_mappings {
+            config
+                .rule_mappings
+                .entry(rule_name.clone())
+                .or_default()
+                .extend(map.clone());
+        }
+
+        if self.ignore.is_some() {
+            config.ignore = self.ignore.clone();
+        }
+        config.overrides.extend(self.overrides.iter().cloned());
+        if self.yaml_files.is_some() {
+            config.yaml_files = self.yaml_files.clone();
+        }
+        config.root = self.root;
+        config
+            .compat_notes
+            .extend(self.compat_notes.iter().cloned());
+
+        config
+    }
+
+    /// Apply `overlay` rule configurations on top of `rules`
+    ///
+    /// A rule given only a level keeps its base options; a rule given options
+    /// changes only those, as when a config `extends` another. `mappings`
+    /// holds the options of the overlay rules as written.
+    fn apply_rules(
+        rules: &mut IndexMap<String, RuleConfig>,
+        overlay: &IndexMap<String, RuleConfig>,
+        mappings: &RuleMappings,
+    ) {
+        for (rule_name, rule_config) in overlay {
+            let merged = match (rules.get(rule_name), rule_config) {
+                (Some(RuleConfig::Detailed { options, .. }), RuleConfig::Level(level)) => {
+                    RuleConfig::Detailed {
+                        level: *level,
+                        options: options.clone(),
+                    }
+                }
+                (Some(base), RuleConfig::Detailed { .. }) => mappings
+                    .get(rule_name)
+                    .and_then(|map| Self::merge_rule_config(rule_name, base, map))
+                    .unwrap_or_else(|| rule_config.clone()),
+                _ => rule_config.clone(),
+            };
+            rules.insert(rule_name.clone(), merged);
+        }
+    }
+
+    /// Configuration of `rule_name` with the options of `map` set on top of
+    /// `base`, or `None` if they don't combine into valid options
+    fn merge_rule_config(
+        rule_name: &str,
+        base: &RuleConfig,
+        map: &serde_yaml::Mapping,
+    ) -> Option<RuleConfig> {
+        let mut merged = base.options().map(Self::options_yaml).unwrap_or_default();
+        // Configuring a disabled rule enables it, as in yamllint
+        if base.level() != RuleLevel::Disable {
+            merged.insert("level".into(), level_name(base.level()).into());
+        }
+        merged.extend(map.clone());
+
+        let level = match merged.get("level") {
+            Some(level) => Self::parse_level(level, rule_name).ok()?,
+            None => RuleLevel::Error,
+        };
+        let options = Self::parse_rule_options(rule_name, &merged, rule_name).ok()??;
+        Some(RuleConfig::Detailed { level, options })
+    }
+
+    /// Indexes of the overrides that apply to `path`, in order
+    pub(crate) fn matching_overrides(&self, path: &Path) -> Vec<usize> {
+        self.overrides
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.matches(path))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Effective config for `path`, with the matching overrides applied
+    pub fn for_path(&self, path: &Path) -> Config {
+        self.with_overrides(&self.matching_overrides(path))
+    }
+
+    /// Apply the overrides at `indexes` on top of the base rules, as in
+    /// ESLint overrides
+    pub(crate) fn with_overrides(&self, indexes: &[usize]) -> Config {
+        let mut config = Config {
+            rules: self.rules.clone(),
+            ignore: self.ignore.clone(),
+            overrides: Vec::new(),
+            yaml_files: self.yaml_files.clone(),
+            root: self.root,
+            compat_notes: self.compat_notes.clone(),
+            rule_mappings: self.rule_mappings.clone(),
+        };
+
+        // An empty rule set means the defaults, which overrides build upon
+        if config.rules.is_empty() && !indexes.is_empty() {
+            config.rules = Self::with_default_preset().rules;
+        }
+
+        for entry in indexes
+            .iter()
+            .filter_map(|&index| self.overrides.get(index))
+        {
+            Self::apply_rules(&mut config.rules, &entry.rules, &entry.rule_mappings);
+        }
+
+        config
+    }
 
-        let present_config = match present_value {
-            Some(serde_yaml::Value::Bool(true)) => DocumentStartConfig::Required,
-            Some(serde_yaml::Value::Bool(false)) => DocumentStartConfig::Forbidden,
-            None => DocumentStartConfig::Disabled,
-            Some(_) => {
-                return Err(crate::LintError::ConfigError(
-                    "document-start 'present' must be a boolean (true or false)".to_string(),
-                ));
-            }
-        };
+    /// Check whether `path` should be linted when walking a directory,
+    /// according to `yaml-files`
+    pub fn is_yaml_file(&self, path: &Path) -> bool {
+        match &self.yaml_files {
+            Some(yaml_files) => yaml_files.matches(path),
+            None => DEFAULT_MATCHER.matches(path),
+        }
+    }
 
-        Ok(RuleOptions::DocumentStart {
-            present: present_config,
-        })
+    /// Check whether `path` is excluded from linting by `ignore`
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        self.ignore
+            .as_ref()
+            .is_some_and(|ignore| ignore.matches(path))
     }
 
-    /// Load config from YAML string
-    pub fn load_from_str(content: &str) -> Result<Self> {
-        let yaml: serde_yaml::Value = serde_yaml::from_str(content)
-            .map_err(|e| crate::LintError::ConfigError(format!("Invalid YAML: {}", e)))?;
+    /// Get the level for a specific rule
+    pub fn get_rule_level(&self, rule_name: &str) -> Option<RuleLevel> {
+        self.rules.get(rule_name).map(|config| config.level())
+    }
 
-        let mut config = Self::new();
+    /// Serialize the config as YAML that loads back to the same behaviour
+    ///
+    /// Every built-in rule is listed with its level and all of its options,
+    /// defaults filled in; rules the config leaves out do not run and are
+    /// listed as disabled. Overrides are written as configured, since a
+    /// level-only override keeps the options of the rule it overrides.
+    pub fn to_yaml(&self) -> String {
+        // An empty rule set means the defaults
+        let base_rules = if self.rules.is_empty() {
+            Self::with_default_preset().rules
+        } else {
+            self.rules.clone()
+        };
 
-        // Check for extends
-        if let Some(extends) = yaml.get("extends").and_then(|v| v.as_str()) {
-            config = match extends {
-                "default" => Self::with_default_preset(),
-                "relaxed" => Self::with_relaxed_preset(),
-                _ => {
-                    return Err(crate::LintError::ConfigError(format!(
-                        "Unknown preset: {}",
-                        extends
-                    )));
-                }
+        let mut rules = serde_yaml::Mapping::new();
+        for rule_name in BUILTIN_RULES {
+            let rule_config = base_rules
+                .get(rule_name)
+                .cloned()
+                .unwrap_or(RuleConfig::Level(RuleLevel::Disable));
+            let options = match rule_config.options() {
+                Some(options) => Some(options.clone()),
+                None => Self::default_options(rule_name),
             };
+
+            let mut rule = serde_yaml::Mapping::new();
+            rule.insert("level".into(), level_name(rule_config.level()).into());
+            if let Some(options) = &options {
+                rule.extend(Self::options_yaml(options));
+            }
+            let ignore: Vec<serde_yaml::Value> = self
+                .overrides
+                .iter()
+                .filter(|entry| entry.from_rule_ignore && entry.rules.contains_key(rule_name))
+                .flat_map(|entry| entry.files.iter().map(|p| p.as_str().into()))
+                .collect();
+            if !ignore.is_empty() {
+                rule.insert("ignore".into(), serde_yaml::Value::Sequence(ignore));
+            }
+            rules.insert(rule_name.into(), serde_yaml::Value::Mapping(rule));
         }
 
-        // Parse rules
-        if let Some(rules) = yaml.get("rules").and_then(|v| v.as_mapping()) {
-            for (key, value) in rules {
-                let rule_name = key.as_str().ok_or_else(|| {
-                    crate::LintError::ConfigError("Rule name must be a string".to_string())
-                })?;
+        let mut yaml = serde_yaml::Mapping::new();
+        if self.root {
+            yaml.insert("root".into(), true.into());
+        }
+        yaml.insert("rules".into(), serde_yaml::Value::Mapping(rules));
+        let yaml_files = match &self.yaml_files {
+            Some(yaml_files) => yaml_files.patterns().to_vec(),
+            None => DEFAULT_YAML_FILES.iter().map(|p| p.to_string()).collect(),
+        };
+        yaml.insert(
+            "yaml-files".into(),
+            serde_yaml::Value::Sequence(yaml_files.into_iter().map(Into::into).collect()),
+        );
+        if let Some(ignore) = &self.ignore {
+            let mut patterns = ignore.patterns().join("\n");
+            patterns.push('\n');
+            yaml.insert("ignore".into(), patterns.into());
+        }
 
-                let rule_config = match value {
-                    // Simple string level: "error", "warning", "disable"
-                    serde_yaml::Value::String(s) => {
-                        let level = match s.as_str() {
-                            "error" => RuleLevel::Error,
-                            "warning" => RuleLevel::Warning,
-                            "disable" => RuleLevel::Disable,
-                            _ => {
-                                return Err(crate::LintError::ConfigError(format!(
-                                    "Invalid rule level: {}",
-                                    s
-                                )));
+        // Rules' ignore patterns are written with the rules
+        let overrides: Vec<&ConfigOverride> = self
+            .overrides
+            .iter()
+            .filter(|entry| !entry.from_rule_ignore)
+            .collect();
+        if !overrides.is_empty() {
+            let overrides = overrides
+                .into_iter()
+                .map(|entry| {
+                    let mut rules = serde_yaml::Mapping::new();
+                    for (rule_name, rule_config) in &entry.rules {
+                        let level = level_name(rule_config.level()).into();
+                        let rule = match rule_config.options() {
+                            Some(options) => {
+                                let mut rule = serde_yaml::Mapping::new();
+                                rule.insert("level".into(), level);
+                                rule.extend(Self::options_yaml(options));
+                                serde_yaml::Value::Mapping(rule)
                             }
+                            None => level,
                         };
-                        RuleConfig::Level(level)
+                        rules.insert(rule_name.as_str().into(), rule);
                     }
-                    // Mapping with options
-                    serde_yaml::Value::Mapping(map) => {
-                        // Extract explicit level if specified, otherwise default to Error
-                        let level = map
-                            .get(serde_yaml::Value::String("level".to_string()))
-                            .and_then(|v| v.as_str())
-                            .map(|s| match s {
-                                "error" => Ok(RuleLevel::Error),
-                                "warning" => Ok(RuleLevel::Warning),
-                                "disable" => Ok(RuleLevel::Disable),
-                                _ => Err(crate::LintError::ConfigError(format!(
-                                    "Invalid rule level: {}",
-                                    s
-                                ))),
-                            })
-                            .transpose()?
-                            .unwrap_or(RuleLevel::Error);
-
-                        // Parse rule-specific options
-                        let options = Self::parse_rule_options(rule_name, map)?;
-
-                        RuleConfig::Detailed { level, options }
-                    }
-                    _ => {
-                        return Err(crate::LintError::ConfigError(
-                            "Rule value must be a string or mapping".to_string(),
-                        ));
-                    }
-                };
 
-                config.rules.insert(rule_name.to_string(), rule_config);
-            }
-        }
-
-        // Parse ignore patterns
-        if let Some(ignore) = yaml.get("ignore").and_then(|v| v.as_str()) {
-            config.ignore = ignore.lines().map(|s| s.to_string()).collect();
+                    let files = entry.files.iter().map(|f| f.as_str().into()).collect();
+                    let mut yaml = serde_yaml::Mapping::new();
+                    yaml.insert("files".into(), serde_yaml::Value::Sequence(files));
+                    yaml.insert("rules".into(), serde_yaml::Value::Mapping(rules));
+                    serde_yaml::Value::Mapping(yaml)
+                })
+                .collect();
+            yaml.insert("overrides".into(), serde_yaml::Value::Sequence(overrides));
         }
 
-        Ok(config)
+        serde_yaml::to_string(&serde_yaml::Value::Mapping(yaml))
+            .expect("config YAML has only string keys")
     }
 
-    /// Find a config file starting from the given directory
-    pub fn find_config_file(start_dir: &Path) -> Option<std::path::PathBuf> {
-        let config_names = [".yamllint", ".yamllint.yml", ".yamllint.yaml"];
+    /// Options a rule uses when configured with a level only, or `None` for
+    /// rules without options
+    fn default_options(rule_name: &str) -> Option<RuleOptions> {
+        Self::parse_rule_options(rule_name, &serde_yaml::Mapping::new(), "")
+            .ok()
+            .flatten()
+    }
 
-        let mut current = start_dir.to_path_buf();
+    /// YAML keys and values of rule options, as accepted in a config file
+    fn options_yaml(options: &RuleOptions) -> serde_yaml::Mapping {
+        let mut yaml = serde_yaml::Mapping::new();
+        let mut set = |key: &str, value: serde_yaml::Value| {
+            yaml.insert(key.into(), value);
+        };
 
-        loop {
-            for name in &config_names {
-                let config_path = current.join(name);
-                if config_path.exists() {
-                    return Some(config_path);
+        match options {
+            RuleOptions::LineLength { max } => set("max", (*max as u64).into()),
+            RuleOptions::Indentation {
+                spaces,
+                indent_sequences,
+                check_multi_line_strings,
+            } => {
+                set(
+                    "spaces",
+                    match spaces {
+                        IndentConfig::Fixed(n) => (*n as u64).into(),
+                        IndentConfig::Consistent => "consistent".into(),
+                    },
+                );
+                set(
+                    "indent-sequences",
+                    match indent_sequences {
+                        IndentSequencesConfig::Indented => true.into(),
+                        IndentSequencesConfig::NotIndented => false.into(),
+                        IndentSequencesConfig::Whatever => "whatever".into(),
+                        IndentSequencesConfig::Consistent => "consistent".into(),
+                    },
+                );
+                set(
+                    "check-multi-line-strings",
+                    (*check_multi_line_strings).into(),
+                );
+            }
+            RuleOptions::Colons {
+                max_spaces_before,
+                max_spaces_after,
+            } => {
+                set("max-spaces-before", limit_yaml(*max_spaces_before));
+                set("max-spaces-after", limit_yaml(*max_spaces_after));
+            }
+            RuleOptions::EmptyLines {
+                max,
+                max_start,
+                max_end,
+            } => {
+                set("max", (*max as u64).into());
+                set("max-start", (*max_start as u64).into());
+                set("max-end", (*max_end as u64).into());
+            }
+            RuleOptions::Hyphens { max_spaces_after } => {
+                set("max-spaces-after", limit_yaml(*max_spaces_after));
+            }
+            RuleOptions::Comments {
+                require_starting_space,
+                ignore_shebangs,
+                min_spaces_from_content,
+            } => {
+                set("require-starting-space", (*require_starting_space).into());
+                set("ignore-shebangs", (*ignore_shebangs).into());
+                set(
+                    "min-spaces-from-content",
+                    (*min_spaces_from_content as u64).into(),
+                );
+            }
+            RuleOptions::Truthy {
+                allowed_values,
+                check_keys,
+                fix_style,
+            } => {
+                let allowed_values = allowed_values.iter().map(|v| v.as_str().into()).collect();
+                set(
+                    "allowed-values",
+                    serde_yaml::Value::Sequence(allowed_values),
+                );
+                set("check-keys", (*check_keys).into());
+                // Without a fix style, truthy values are not auto-fixed
+                if let Some(fix_style) = fix_style {
+                    set(
+                        "fix-style",
+                        match fix_style {
+                            TruthyFixStyleConfig::Canonical => "canonical".into(),
+                            TruthyFixStyleConfig::Quote => "quote".into(),
+                        },
+                    );
                 }
             }
-
-            // Move up one directory
-            if !current.pop() {
-                break;
+            RuleOptions::DocumentStart {
+                present,
+                insert_after_comments,
+            } => {
+                // Without `present`, the marker is neither required nor
+                // forbidden
+                match present {
+                    DocumentStartConfig::Required => set("present", true.into()),
+                    DocumentStartConfig::Forbidden => set("present", false.into()),
+                    DocumentStartConfig::Disabled => {}
+                }
+                set("insert-after-comments", (*insert_after_comments).into());
             }
         }
 
-        None
-    }
-
-    /// Get the level for a specific rule
-    pub fn get_rule_level(&self, rule_name: &str) -> Option<RuleLevel> {
-        self.rules.get(rule_name).map(|config| config.level())
+        yaml
     }
 
     /// Create a RuleRegistry configured according to this Config
-    pub fn create_registry(&self) -> crate::rules::RuleRegistry {
+    ///
+    /// Fails with `LintError::UnknownRule` if a rule name is not a built-in
+    /// rule.
+    pub fn create_registry(&self) -> Result<crate::rules::RuleRegistry> {
         // Macro to reduce boilerplate when constructing rules with options
         macro_rules! construct_rule {
             // Rule without options
@@ -509,7 +1855,7 @@ impl Config {
 
         // If no rules configured, use defaults
         if self.rules.is_empty() {
-            return crate::rules::RuleRegistry::with_defaults();
+            return Ok(crate::rules::RuleRegistry::with_defaults());
         }
 
         // Register each rule with its configuration
@@ -528,21 +1874,23 @@ impl Config {
                     crate::rules::line_length::LineLengthRule::new()
                 ),
                 "document-start" => {
-                    if let Some(RuleOptions::DocumentStart { present }) = rule_config.options() {
-                        match present {
-                            DocumentStartConfig::Required => {
-                                Box::new(crate::rules::document_start::DocumentStartRule::required())
-                            }
-                            DocumentStartConfig::Forbidden => Box::new(
-                                crate::rules::document_start::DocumentStartRule::forbidden(),
-                            ),
-                            DocumentStartConfig::Disabled => {
-                                Box::new(crate::rules::document_start::DocumentStartRule::new())
-                            }
-                        }
-                    } else {
-                        Box::new(crate::rules::document_start::DocumentStartRule::new())
-                    }
+                    use crate::rules::document_start::{DocumentStartPresence, DocumentStartRule};
+
+                    construct_rule!(
+                        rule_config,
+                        RuleOptions::DocumentStart {
+                            present,
+                            insert_after_comments,
+                        } => DocumentStartRule::with_config(
+                            match present {
+                                DocumentStartConfig::Required => DocumentStartPresence::Required,
+                                DocumentStartConfig::Forbidden => DocumentStartPresence::Forbidden,
+                                DocumentStartConfig::Disabled => DocumentStartPresence::Disabled,
+                            },
+                            *insert_after_comments
+                        ),
+                        DocumentStartRule::new()
+                    )
                 }
                 "colons" => construct_rule!(
                     rule_config,
@@ -559,18 +1907,29 @@ impl Config {
                     construct_rule!(crate::rules::key_duplicates::KeyDuplicatesRule)
                 }
                 "indentation" => {
-                    if let Some(RuleOptions::Indentation { spaces }) = rule_config.options() {
-                        match spaces {
-                            IndentConfig::Fixed(n) => Box::new(
-                                crate::rules::indentation::IndentationRule::with_spaces(*n),
-                            ),
-                            IndentConfig::Consistent => {
-                                Box::new(crate::rules::indentation::IndentationRule::consistent())
-                            }
-                        }
-                    } else {
-                        Box::new(crate::rules::indentation::IndentationRule::new())
-                    }
+                    use crate::rules::indentation::{IndentSequences, IndentSpaces};
+
+                    construct_rule!(
+                        rule_config,
+                        RuleOptions::Indentation {
+                            spaces,
+                            indent_sequences,
+                            check_multi_line_strings,
+                        } => crate::rules::indentation::IndentationRule::with_config(
+                            match spaces {
+                                IndentConfig::Fixed(n) => IndentSpaces::Fixed(*n),
+                                IndentConfig::Consistent => IndentSpaces::Consistent,
+                            },
+                            match indent_sequences {
+                                IndentSequencesConfig::Indented => IndentSequences::Indented,
+                                IndentSequencesConfig::NotIndented => IndentSequences::NotIndented,
+                                IndentSequencesConfig::Whatever => IndentSequences::Whatever,
+                                IndentSequencesConfig::Consistent => IndentSequences::Consistent,
+                            },
+                            *check_multi_line_strings
+                        ),
+                        crate::rules::indentation::IndentationRule::new()
+                    )
                 }
                 "new-line-at-end-of-file" => {
                     construct_rule!(crate::rules::new_line_at_end_of_file::NewLineAtEndOfFileRule)
@@ -612,20 +1971,27 @@ impl Config {
                     RuleOptions::Truthy {
                         allowed_values,
                         check_keys,
+                        fix_style,
                     } => crate::rules::truthy::TruthyRule::with_config(
                         allowed_values.clone(),
-                        *check_keys
+                        *check_keys,
+                        fix_style.map(|style| match style {
+                            TruthyFixStyleConfig::Canonical => {
+                                crate::rules::truthy::TruthyFixStyle::Canonical
+                            }
+                            TruthyFixStyleConfig::Quote => crate::rules::truthy::TruthyFixStyle::Quote,
+                        })
                     ),
                     crate::rules::truthy::TruthyRule::new()
                 ),
-                _ => continue, // Skip unknown rules
+                _ => return Err(unknown_rule(rule_name)),
             };
 
             registry.register(rule);
             registry.set_level(rule_name, level);
         }
 
-        registry
+        Ok(registry)
     }
 }
 
@@ -635,6 +2001,190 @@ impl Default for Config {
     }
 }
 
+/// Options of each rule as written in a config file, kept to combine them
+/// with the options of the config they are layered on
+type RuleMappings = HashMap<String, serde_yaml::Mapping>;
+
+/// yamllint settings found while parsing rules, see [`crate::compat`]
+#[derive(Debug, Default)]
+struct RuleCompat {
+    notes: Vec<CompatNote>,
+    /// `ignore` patterns of each rule setting them
+    ignores: Vec<(String, Vec<String>)>,
+}
+
+/// Mapping of options found at a YAML path, with typed accessors whose errors
+/// name that path
+struct OptionMap<'a> {
+    map: &'a serde_yaml::Mapping,
+    path: &'a str,
+}
+
+impl<'a> OptionMap<'a> {
+    /// Wrap `map`, rejecting keys not in `known`
+    fn new(map: &'a serde_yaml::Mapping, path: &'a str, known: &[&str]) -> Result<Self> {
+        let options = Self { map, path };
+
+        for key in map.keys() {
+            let Some(key) = key.as_str() else {
+                return Err(crate::LintError::ConfigError(format!(
+                    "`{}`: keys must be strings, found {}",
+                    path,
+                    describe(key)
+                )));
+            };
+
+            if known.contains(&key) {
+                continue;
+            }
+
+            return Err(crate::LintError::ConfigError(format!(
+                "unknown key `{}`{}",
+                options.key_path(key),
+                did_you_mean(key, known)
+            )));
+        }
+
+        Ok(options)
+    }
+
+    /// Value of `key`, if set
+    fn get(&self, key: &str) -> Option<&'a serde_yaml::Value> {
+        self.map.get(key)
+    }
+
+    /// Full YAML path of `key`
+    fn key_path(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        }
+    }
+
+    /// Error about the value of `key`
+    fn error(&self, key: &str, message: &str) -> crate::LintError {
+        crate::LintError::ConfigError(format!("`{}` {}", self.key_path(key), message))
+    }
+
+    /// Non-negative integer value of `key`, or `default` when unset
+    fn usize(&self, key: &str, default: usize) -> Result<usize> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(value) => value.as_u64().map(|n| n as usize).ok_or_else(|| {
+                self.error(
+                    key,
+                    &format!("must be a non-negative integer, found {}", describe(value)),
+                )
+            }),
+        }
+    }
+
+    /// Like [`Self::usize`], also accepting `-1`, which yamllint uses to
+    /// turn a limit off, as `off`
+    fn usize_or_off(&self, key: &str, default: usize, off: usize) -> Result<usize> {
+        match self.get(key) {
+            Some(value) if value.as_i64() == Some(-1) => Ok(off),
+            Some(value) if value.as_u64().is_none() => Err(self.error(
+                key,
+                &format!(
+                    "must be a non-negative integer or -1, found {}",
+                    describe(value)
+                ),
+            )),
+            _ => self.usize(key, default),
+        }
+    }
+
+    /// Boolean value of `key`, or `default` when unset
+    fn bool(&self, key: &str, default: bool) -> Result<bool> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(value) => value.as_bool().ok_or_else(|| {
+                self.error(
+                    key,
+                    &format!(
+                        "must be a boolean (true or false), found {}",
+                        describe(value)
+                    ),
+                )
+            }),
+        }
+    }
+}
+
+/// Name of a rule level in config files
+fn level_name(level: RuleLevel) -> &'static str {
+    match level {
+        RuleLevel::Error => "error",
+        RuleLevel::Warning => "warning",
+        RuleLevel::Disable => "disable",
+    }
+}
+
+/// YAML for a maximum, `-1` for no limit
+fn limit_yaml(max: usize) -> serde_yaml::Value {
+    match max {
+        usize::MAX => (-1).into(),
+        max => (max as u64).into(),
+    }
+}
+
+/// Describe a YAML value for error messages, e.g. `string "120"`
+fn describe(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::Null => "null".to_string(),
+        serde_yaml::Value::Bool(b) => format!("boolean {}", b),
+        serde_yaml::Value::Number(n) => format!("number {}", n),
+        serde_yaml::Value::String(s) => format!("string {:?}", s),
+        serde_yaml::Value::Sequence(_) => "a list".to_string(),
+        serde_yaml::Value::Mapping(_) => "a mapping".to_string(),
+        serde_yaml::Value::Tagged(tagged) => format!("tagged value {}", tagged.tag),
+    }
+}
+
+/// Error for a rule name that is not a built-in rule, suggesting the
+/// closest built-in rule
+pub fn unknown_rule(rule_name: &str) -> crate::LintError {
+    crate::LintError::UnknownRule(format!(
+        "{}{}",
+        rule_name,
+        did_you_mean(rule_name, &BUILTIN_RULES)
+    ))
+}
+
+/// `, did you mean `x`?` when one of `candidates` is close to `input`
+fn did_you_mean(input: &str, candidates: &[&str]) -> String {
+    let closest = candidates
+        .iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(2))
+        .min_by_key(|(distance, _)| *distance);
+
+    match closest {
+        Some((_, candidate)) => format!(", did you mean `{}`?", candidate),
+        None if candidates.is_empty() => String::new(),
+        None => format!(" (expected one of: {})", candidates.join(", ")),
+    }
+}
+
+/// Levenshtein distance between `a` and `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -728,8 +2278,14 @@ rules:
 
         match rule_config {
             RuleConfig::Detailed { options, .. } => match options {
-                RuleOptions::Indentation { spaces } => {
-                    assert_eq!(*spaces, IndentConfig::Fixed(2))
+                RuleOptions::Indentation {
+                    spaces,
+                    indent_sequences,
+                    check_multi_line_strings,
+                } => {
+                    assert_eq!(*spaces, IndentConfig::Fixed(2));
+                    assert_eq!(*indent_sequences, IndentSequencesConfig::Indented);
+                    assert!(!check_multi_line_strings);
                 }
                 _ => panic!("Expected Indentation options"),
             },
@@ -761,6 +2317,401 @@ rules:
         );
     }
 
+    #[test]
+    fn test_extends_merges_options_with_preset_level() {
+        let yaml = r#"
+extends: relaxed
+rules:
+  line-length:
+    max: 120
+"#;
+        let config = Config::load_from_str(yaml).unwrap();
+        assert_eq!(
+            config.rules.get("line-length"),
+            Some(&RuleConfig::Detailed {
+                level: RuleLevel::Warning,
+                options: RuleOptions::LineLength { max: 120 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_extends_list_of_presets() {
+        let yaml = r#"
+extends: [relaxed, default]
+"#;
+        let config = Config::load_from_str(yaml).unwrap();
+        assert_eq!(
+            config.get_rule_level("trailing-spaces"),
+            Some(RuleLevel::Error)
+        );
+    }
+
+    #[test]
+    fn test_extends_invalid_type() {
+        let result = Config::load_from_str("extends: 3");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_overrides_later_entries_win() {
+        let yaml = r#"
+rules:
+  line-length:
+    max: 100
+overrides:
+  - files: ["k8s/**", "*.k8s.yaml"]
+    rules:
+      line-length: warning
+  - files: "k8s/generated/**"
+    rules:
+      line-length: disable
+"#;
+        let config = Config::load_from_str(yaml).unwrap();
+        assert_eq!(config.overrides.len(), 2);
+
+        // A level-only override keeps the base options
+        assert_eq!(
+            config
+                .for_path(Path::new("k8s/app.yaml"))
+                .rules
+                .get("line-length"),
+            Some(&RuleConfig::Detailed {
+                level: RuleLevel::Warning,
+                options: RuleOptions::LineLength { max: 100 },
+            })
+        );
+        assert_eq!(
+            config
+                .for_path(Path::new("deploy/app.k8s.yaml"))
+                .get_rule_level("line-length"),
+            Some(RuleLevel::Warning)
+        );
+        assert_eq!(
+            config
+                .for_path(Path::new("k8s/generated/crd.yaml"))
+                .get_rule_level("line-length"),
+            Some(RuleLevel::Disable)
+        );
+        assert_eq!(
+            config
+                .for_path(Path::new("docs/k8s/app.yaml"))
+                .get_rule_level("line-length"),
+            Some(RuleLevel::Error)
+        );
+    }
+
+    #[test]
+    fn test_overrides_merge_options() {
+        let yaml = r#"
+rules:
+  indentation:
+    level: warning
+    spaces: 4
+    indent-sequences: false
+overrides:
+  - files: "k8s/**"
+    rules:
+      indentation:
+        spaces: 2
+"#;
+        let config = Config::load_from_str(yaml).unwrap();
+
+        // Options the override leaves unset keep their base values
+        let expected = RuleConfig::Detailed {
+            level: RuleLevel::Warning,
+            options: RuleOptions::Indentation {
+                spaces: IndentConfig::Fixed(2),
+                indent_sequences: IndentSequencesConfig::NotIndented,
+                check_multi_line_strings: false,
+            },
+        };
+        let k8s = config.for_path(Path::new("k8s/app.yaml"));
+        assert_eq!(k8s.rules.get("indentation"), Some(&expected));
+
+        // The same holds for a config layered on an enclosing one
+        let child = Config::load_from_str("rules:\n  indentation:\n    spaces: 2\n").unwrap();
+        let layered = child.layered_on(&config);
+        assert_eq!(layered.rules.get("indentation"), Some(&expected));
+    }
+
+    #[test]
+    fn test_overrides_invalid_entries() {
+        let missing_files = "overrides:\n  - rules:\n      truthy: disable\n";
+        assert!(Config::load_from_str(missing_files).is_err());
+
+        let bad_glob = "overrides:\n  - files: [\"k8s/[\"]\n";
+        let err = Config::load_from_str(bad_glob).unwrap_err().to_string();
+        assert!(err.contains("Invalid overrides glob"), "{}", err);
+
+        let bad_rule = "overrides:\n  - files: [\"*.yaml\"]\n    rules:\n      truthy: loud\n";
+        assert!(Config::load_from_str(bad_rule).is_err());
+    }
+
+    #[test]
+    fn test_global_config_file_precedence() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/fixtures/config");
+        let env = |vars: Vec<(&'static str, PathBuf)>| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.clone().into_os_string())
+            }
+        };
+
+        // The env var wins, even over an existing user config
+        let path = Config::global_config_file_from(env(vec![
+            (CONFIG_FILE_ENV, PathBuf::from("team.yaml")),
+            ("XDG_CONFIG_HOME", fixtures.join("xdg")),
+        ]));
+        assert_eq!(path, Some(PathBuf::from("team.yaml")));
+
+        let path =
+            Config::global_config_file_from(env(vec![("XDG_CONFIG_HOME", fixtures.join("xdg"))]));
+        assert_eq!(path, Some(fixtures.join("xdg/yamllint/config")));
+
+        // ~/.config is only used without XDG_CONFIG_HOME
+        let path = Config::global_config_file_from(env(vec![("HOME", fixtures.join("home"))]));
+        assert_eq!(path, Some(fixtures.join("home/.config/yamllint/config")));
+
+        let path = Config::global_config_file_from(env(vec![
+            ("XDG_CONFIG_HOME", fixtures.join("missing")),
+            ("HOME", fixtures.join("home")),
+        ]));
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        assert_eq!(
+            did_you_mean(
+                "max-spaces-afer",
+                &["max-spaces-before", "max-spaces-after"]
+            ),
+            ", did you mean `max-spaces-after`?"
+        );
+        assert_eq!(
+            did_you_mean("colour", &["max", "level"]),
+            " (expected one of: max, level)"
+        );
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_builtin_rules_match_registry() {
+        let registry = crate::rules::RuleRegistry::with_defaults();
+        assert_eq!(registry.rule_names(), BUILTIN_RULES);
+    }
+
+    #[test]
+    fn test_rule_metadata_matches_options() {
+        let registry = crate::rules::RuleRegistry::with_defaults();
+
+        for rule_name in BUILTIN_RULES {
+            let metadata = registry.get(rule_name).unwrap().metadata();
+            let defaults = Config::default_options(rule_name)
+                .map(|options| Config::options_yaml(&options))
+                .unwrap_or_default();
+
+            for option in metadata.options {
+                let default = option
+                    .default
+                    .map(|default| serde_yaml::from_str::<serde_yaml::Value>(default).unwrap());
+                assert_eq!(
+                    defaults.get(option.name),
+                    default.as_ref(),
+                    "{}.{}",
+                    rule_name,
+                    option.name
+                );
+            }
+            for key in defaults.keys() {
+                assert!(
+                    metadata
+                        .options
+                        .iter()
+                        .any(|option| key.as_str() == Some(option.name)),
+                    "{}.{:?} is not documented",
+                    rule_name,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_yaml_fills_in_defaults() {
+        let config = Config::load_from_str(
+            r#"
+rules:
+  line-length:
+    max: 120
+  truthy: disable
+  document-start:
+    present: true
+"#,
+        )
+        .unwrap();
+
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&config.to_yaml()).unwrap();
+        let rules = &yaml["rules"];
+        assert_eq!(rules["line-length"]["level"], "error");
+        assert_eq!(rules["line-length"]["max"], 120);
+        assert_eq!(rules["truthy"]["level"], "disable");
+        assert_eq!(rules["truthy"]["check-keys"], false);
+        assert_eq!(rules["document-start"]["present"], true);
+        assert_eq!(rules["document-start"]["insert-after-comments"], true);
+        // Rules left out of the config do not run
+        assert_eq!(rules["colons"]["level"], "disable");
+        assert_eq!(rules["colons"]["max-spaces-after"], 1);
+        assert_eq!(rules["key-duplicates"]["level"], "disable");
+    }
+
+    #[test]
+    fn test_to_yaml_round_trips() {
+        let config = Config::load_from_str(
+            r#"
+extends: default
+rules:
+  indentation:
+    spaces: 2
+    indent-sequences: whatever
+  truthy:
+    allowed-values: ["yes", "no"]
+    fix-style: quote
+  key-duplicates:
+    ignore: ["fixtures/"]
+ignore: |
+  vendor/
+overrides:
+  - files: ["*.yml"]
+    rules:
+      line-length: warning
+"#,
+        )
+        .unwrap();
+
+        let reloaded = Config::load_from_str(&config.to_yaml()).unwrap();
+        for (rule_name, rule_config) in &reloaded.rules {
+            let expected = match config.rules.get(rule_name) {
+                Some(RuleConfig::Level(level)) => match Config::default_options(rule_name) {
+                    Some(options) => RuleConfig::Detailed {
+                        level: *level,
+                        options,
+                    },
+                    None => RuleConfig::Level(*level),
+                },
+                Some(detailed) => detailed.clone(),
+                None => panic!("unexpected rule {}", rule_name),
+            };
+            assert_eq!(rule_config, &expected, "{}", rule_name);
+        }
+        assert_eq!(reloaded.rules.len(), config.rules.len());
+        assert_eq!(
+            reloaded.ignore.as_ref().map(IgnorePatterns::patterns),
+            config.ignore.as_ref().map(IgnorePatterns::patterns)
+        );
+        assert_eq!(reloaded.overrides.len(), 2);
+        for (reloaded, entry) in reloaded.overrides.iter().zip(&config.overrides) {
+            assert_eq!(reloaded.files, entry.files);
+            assert_eq!(reloaded.rules, entry.rules);
+        }
+    }
+
+    #[test]
+    fn test_yamllint_config() {
+        let config = Config::load_from_str(
+            r#"
+extends: default
+locale: en_US.UTF-8
+ignore:
+  - vendor/
+  - "!vendor/keep.yaml"
+rules:
+  braces: enable
+  brackets: disable
+  colons:
+    max-spaces-after: -1
+  document-start: disable
+  empty-lines: enable
+  line-length:
+    level: warning
+    max: 120
+    allow-non-breakable-words: true
+    allow-non-breakable-inline-mappings: false
+  truthy:
+    check-keys: false
+    ignore: |
+      .github/workflows/
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.get_rule_level("empty-lines"), Some(RuleLevel::Error));
+        assert_eq!(
+            config.rules["colons"].options(),
+            Some(&RuleOptions::Colons {
+                max_spaces_before: 0,
+                max_spaces_after: usize::MAX,
+            })
+        );
+        assert!(config.to_yaml().contains("max-spaces-after: -1\n"));
+
+        assert!(config.is_ignored(Path::new("vendor/lib/app.yaml")));
+        assert!(!config.is_ignored(Path::new("vendor/keep.yaml")));
+        assert!(!config.is_ignored(Path::new("app.yaml")));
+        let workflow = Path::new(".github/workflows/ci.yaml");
+        assert_eq!(
+            config.for_path(workflow).get_rule_level("truthy"),
+            Some(RuleLevel::Disable)
+        );
+
+        let notes: Vec<String> = config
+            .compat_notes
+            .iter()
+            .map(|note| format!("{} {}", note.kind, note.path))
+            .collect();
+        assert_eq!(
+            notes,
+            [
+                "unsupported rules.line-length.allow-non-breakable-words",
+                "unsupported rules.braces",
+                "unsupported locale",
+            ]
+        );
+
+        // Keys unknown to yamllint too are still errors
+        let error = Config::load_from_str(
+            "rules:
+  brace: enable
+",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("did you mean `braces`?"), "{}", error);
+        let error = Config::load_from_str(
+            "ignore: vendor/
+ignore-from-file: .gitignore
+",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("cannot be used together"), "{}", error);
+    }
+
+    #[test]
+    fn test_yaml_files_layering() {
+        let parent = Config::load_from_str("yaml-files: ['*.yaml', '*.j2']\n").unwrap();
+        let child = Config::load_from_str("rules:\n  truthy: disable\n").unwrap();
+        let layered = child.layered_on(&parent);
+        assert!(layered.is_yaml_file(Path::new("app.j2")));
+
+        let child = Config::load_from_str("yaml-files: ['*.yml']\n").unwrap();
+        let layered = child.layered_on(&parent);
+        assert!(!layered.is_yaml_file(Path::new("app.j2")));
+        assert!(layered.is_yaml_file(Path::new("app.yml")));
+    }
+
     #[test]
     fn test_invalid_preset() {
         let yaml = "extends: nonexistent";
@@ -0,0 +1,218 @@
+//! `# yamllint` comment directives
+//!
+//! Directives turn rules off for part of a file, using the same syntax as
+//! yamllint:
+//!
+//! - `# yamllint disable-line [rule:NAME ...]` disables rules on its own
+//!   line, or on the next line when the comment stands on a line by itself
+//! - `# yamllint disable [rule:NAME ...]` disables rules until a matching
+//!   `# yamllint enable [rule:NAME ...]`
+//! - `# yamllint disable-file` on the first line disables every rule
+//!
+//! Without `rule:` arguments a directive applies to all rules.
+
+use crate::rules::LintContext;
+use crate::rules::comments::CommentsRule;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+static DIRECTIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^#\s*yamllint\s+(disable-line|disable-file|disable|enable)((?:\s+rule:[\w-]+)*)\s*$",
+    )
+    .expect("directive regex is valid")
+});
+
+/// Rules disabled by the directives of a file
+#[derive(Debug, Default)]
+pub struct Directives {
+    /// Whether `# yamllint disable-file` disables the whole file
+    file_disabled: bool,
+    /// State set by `disable`/`enable` directives, with the line it starts on
+    blocks: Vec<(usize, BlockState)>,
+    /// Rules disabled on single lines by `disable-line`, `None` for all rules
+    lines: HashMap<usize, Option<HashSet<String>>>,
+}
+
+/// Rules disabled from a `disable` or `enable` directive on
+#[derive(Debug, Clone, Default)]
+struct BlockState {
+    /// Whether all rules are disabled, except the listed ones
+    all: bool,
+    /// Disabled rules, or re-enabled ones when `all` is set
+    listed: HashSet<String>,
+}
+
+impl BlockState {
+    fn is_disabled(&self, rule: &str) -> bool {
+        self.all != self.listed.contains(rule)
+    }
+
+    fn disable(&mut self, rules: &[String]) {
+        if rules.is_empty() {
+            self.all = true;
+            self.listed.clear();
+        }
+        for rule in rules {
+            if self.all {
+                self.listed.remove(rule);
+            } else {
+                self.listed.insert(rule.clone());
+            }
+        }
+    }
+
+    fn enable(&mut self, rules: &[String]) {
+        if rules.is_empty() {
+            self.all = false;
+            self.listed.clear();
+        }
+        for rule in rules {
+            if self.all {
+                self.listed.insert(rule.clone());
+            } else {
+                self.listed.remove(rule);
+            }
+        }
+    }
+}
+
+impl Directives {
+    /// Find the directives in the comments of `context`
+    pub fn parse(context: &LintContext) -> Self {
+        let mut directives = Self::default();
+        let mut block = BlockState::default();
+
+        for (index, line) in context.lines.iter().enumerate() {
+            let line_number = index + 1;
+            let Some(start) = CommentsRule::find_comment_start(line) else {
+                continue;
+            };
+            let Some(captures) = DIRECTIVE.captures(line[start..].trim_end()) else {
+                continue;
+            };
+
+            let rules: Vec<String> = captures[2]
+                .split_whitespace()
+                .filter_map(|arg| arg.strip_prefix("rule:"))
+                .map(str::to_string)
+                .collect();
+
+            match &captures[1] {
+                "disable-line" => {
+                    // A comment on a line of its own applies to the next line
+                    let own_line = line[..start].trim().is_empty();
+                    let target = if own_line {
+                        line_number + 1
+                    } else {
+                        line_number
+                    };
+                    let entry = directives
+                        .lines
+                        .entry(target)
+                        .or_insert_with(|| Some(HashSet::new()));
+                    if rules.is_empty() {
+                        *entry = None;
+                    } else if let Some(disabled) = entry {
+                        disabled.extend(rules);
+                    }
+                }
+                "disable-file" => directives.file_disabled |= line_number == 1,
+                "disable" => {
+                    block.disable(&rules);
+                    directives.blocks.push((line_number, block.clone()));
+                }
+                _ => {
+                    block.enable(&rules);
+                    directives.blocks.push((line_number, block.clone()));
+                }
+            }
+        }
+
+        directives
+    }
+
+    /// Check whether `rule` is disabled on `line` (1-based)
+    pub fn is_disabled(&self, rule: &str, line: usize) -> bool {
+        if self.file_disabled {
+            return true;
+        }
+
+        let disabled_on_line = self.lines.get(&line).is_some_and(|rules| match rules {
+            Some(rules) => rules.contains(rule),
+            None => true,
+        });
+        if disabled_on_line {
+            return true;
+        }
+
+        // The last block directive at or before the line
+        let index = self.blocks.partition_point(|(start, _)| *start <= line);
+        index > 0 && self.blocks[index - 1].1.is_disabled(rule)
+    }
+}
+
+/// Comment disabling `rule` on the line below it
+pub fn disable_line_comment(rule: &str) -> String {
+    format!("# yamllint disable-line rule:{}", rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directives(content: &str) -> Directives {
+        Directives::parse(&LintContext::new(content.to_string()))
+    }
+
+    #[test]
+    fn test_disable_line() {
+        let d = directives(
+            "a: 1  # yamllint disable-line rule:colons\n\
+             # yamllint disable-line\n\
+             b: 2\n\
+             c: 3\n",
+        );
+        assert!(d.is_disabled("colons", 1));
+        assert!(!d.is_disabled("truthy", 1));
+        assert!(d.is_disabled("truthy", 3));
+        assert!(!d.is_disabled("colons", 4));
+    }
+
+    #[test]
+    fn test_disable_and_enable_blocks() {
+        let d = directives(
+            "# yamllint disable rule:truthy rule:colons\n\
+             a: yes\n\
+             # yamllint enable rule:colons\n\
+             b: yes\n\
+             # yamllint enable\n\
+             c: yes\n\
+             # yamllint disable\n\
+             # yamllint enable rule:truthy\n\
+             d: yes\n",
+        );
+        assert!(d.is_disabled("truthy", 2));
+        assert!(d.is_disabled("colons", 2));
+        assert!(d.is_disabled("truthy", 4));
+        assert!(!d.is_disabled("colons", 4));
+        assert!(!d.is_disabled("truthy", 6));
+        assert!(!d.is_disabled("truthy", 9));
+        assert!(d.is_disabled("colons", 9));
+    }
+
+    #[test]
+    fn test_disable_file() {
+        assert!(directives("# yamllint disable-file\na: yes\n").is_disabled("truthy", 2));
+        assert!(!directives("a: 1\n# yamllint disable-file\nb: yes\n").is_disabled("truthy", 3));
+    }
+
+    #[test]
+    fn test_directives_need_a_comment() {
+        let d = directives("a: '# yamllint disable'\nb: yes\n");
+        assert!(!d.is_disabled("truthy", 2));
+        let d = directives("# yamllint disabled\nb: yes\n");
+        assert!(!d.is_disabled("truthy", 2));
+    }
+}
@@ -0,0 +1,118 @@
+//! Per-directory config discovery
+
+use crate::Result;
+use crate::config::Config;
+use crate::linter::Linter;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Resolves the config applying to each linted file from the config files in
+/// its directory and the directories above it
+///
+/// Starting from the file's directory, every config file found on the way up
+/// is layered on top of the ones above it. The search stops at a config with
+/// `root: true` or at the repository root (a directory containing `.git`).
+/// Files with no config file above them use the fallback config.
+///
+/// Results are cached per directory, so each config file is only read once.
+#[derive(Debug)]
+pub struct ConfigResolver {
+    fallback: Arc<Linter>,
+    /// Config for each directory visited, `None` when no config file applies
+    resolved: HashMap<PathBuf, Option<Resolved>>,
+}
+
+/// Layered config of a directory
+#[derive(Debug, Clone)]
+struct Resolved {
+    linter: Arc<Linter>,
+    /// Config files the config was built from, outermost first
+    files: Vec<PathBuf>,
+}
+
+impl ConfigResolver {
+    /// Create a resolver using `fallback` for files without a config file
+    pub fn new(fallback: Config) -> Result<Self> {
+        Ok(Self {
+            fallback: Arc::new(Linter::new(fallback)?),
+            resolved: HashMap::new(),
+        })
+    }
+
+    /// Linter configured for the file at `path`
+    pub fn linter_for(&mut self, path: &Path) -> Result<Arc<Linter>> {
+        Ok(match self.resolve_file(path)? {
+            Some(resolved) => resolved.linter,
+            None => Arc::clone(&self.fallback),
+        })
+    }
+
+    /// Config files applying to the file at `path`, outermost first
+    ///
+    /// Empty when the file uses the fallback config.
+    pub fn config_files_for(&mut self, path: &Path) -> Result<Vec<PathBuf>> {
+        Ok(self
+            .resolve_file(path)?
+            .map(|resolved| resolved.files)
+            .unwrap_or_default())
+    }
+
+    /// Forget all resolved configs, e.g. after a config file changed
+    pub fn clear(&mut self) {
+        self.resolved.clear();
+    }
+
+    /// Resolve the layered config for the directory of `path`
+    fn resolve_file(&mut self, path: &Path) -> Result<Option<Resolved>> {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        self.resolve_dir(&dir.canonicalize()?)
+    }
+
+    /// Resolve the layered config for the canonical directory `dir`
+    fn resolve_dir(&mut self, dir: &Path) -> Result<Option<Resolved>> {
+        if let Some(resolved) = self.resolved.get(dir) {
+            return Ok(resolved.clone());
+        }
+
+        let is_repo_root = dir.join(".git").exists();
+        let parent = match dir.parent() {
+            Some(parent) if !is_repo_root => Some(parent),
+            _ => None,
+        };
+
+        let resolved = match Config::config_file_in(dir) {
+            Some(config_path) => {
+                let config = Config::load_from_file(&config_path)?;
+                let enclosing = match parent {
+                    Some(parent) if !config.root => self.resolve_dir(parent)?,
+                    _ => None,
+                };
+
+                let (config, mut files) = match enclosing {
+                    Some(enclosing) => (
+                        config.layered_on(enclosing.linter.config()),
+                        enclosing.files,
+                    ),
+                    None => (config, Vec::new()),
+                };
+                files.push(config_path);
+
+                Some(Resolved {
+                    linter: Arc::new(Linter::new(config)?),
+                    files,
+                })
+            }
+            None => match parent {
+                Some(parent) => self.resolve_dir(parent)?,
+                None => None,
+            },
+        };
+
+        self.resolved.insert(dir.to_path_buf(), resolved.clone());
+        Ok(resolved)
+    }
+}
@@ -1,8 +1,11 @@
 //! Auto-fix functionality for lint problems
 
-use crate::problem::LintProblem;
+use crate::problem::{LintProblem, TextEdit, apply_edits};
 use crate::rules::{LintContext, RuleRegistry};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Upper bound on fix passes, guarding against fixes that undo each other
+const MAX_FIX_PASSES: usize = 50;
 
 /// Result of a fix operation for a single file
 #[derive(Debug, Clone)]
@@ -40,6 +43,12 @@ impl FixResult {
     pub fn has_unfixable(&self) -> bool {
         !self.unfixable_problems.is_empty()
     }
+
+    /// Count a fix applied by the given rule
+    fn record(&mut self, rule: &str) {
+        self.fixes_applied += 1;
+        *self.fixes_by_rule.entry(rule.to_string()).or_insert(0) += 1;
+    }
 }
 
 /// Fixer that can automatically fix lint problems
@@ -56,60 +65,124 @@ impl<'a> Fixer<'a> {
 
     /// Fix all fixable problems in the given content
     /// Returns the fix result including the fixed content
-    #[allow(clippy::collapsible_if)] // Nested ifs required for MSRV 1.85 compatibility (let chains unstable)
+    ///
+    /// Each pass applies every non-conflicting fix attached to the current
+    /// problems at once, then lints the result again. That picks up fixes
+    /// deferred because they overlapped an earlier one and problems a fix
+    /// revealed, and finds what is left. Fixing stops after a pass that
+    /// changes nothing, so when no fixes overlap the content is fixed in one
+    /// pass and linted twice.
     pub fn fix(&self, path: &str, content: &str) -> FixResult {
         let mut result = FixResult::new(path.to_string());
         let mut current_content = content.to_string();
-        let mut made_progress = true;
+        let mut problems = self
+            .registry
+            .check_all(&LintContext::new(current_content.clone()));
 
-        // Iteratively fix problems until no more fixes can be applied
-        // This handles cases where fixing one problem might reveal or affect others
-        while made_progress {
-            made_progress = false;
+        for _ in 0..MAX_FIX_PASSES {
+            let Some(fixed) = self.apply_fixes(&current_content, &problems, &mut result) else {
+                break;
+            };
 
-            let context = LintContext::new(current_content.clone());
-            let problems = self.registry.check_all(&context);
+            current_content = fixed;
+            problems = self
+                .registry
+                .check_all(&LintContext::new(current_content.clone()));
+        }
 
-            if problems.is_empty() {
-                break;
+        // Whatever is left could not be fixed
+        result.unfixable_problems = problems;
+
+        if result.fixes_applied > 0 {
+            result.fixed_content = Some(current_content);
+        }
+
+        result
+    }
+
+    /// Apply one pass of fixes to `content`, returning the new content if
+    /// anything changed
+    ///
+    /// Problems are visited in order, so when two fixes overlap the first one
+    /// wins and the other is left for the next pass. Rules that rewrite the
+    /// whole file instead of attaching edits are only used when no edits
+    /// apply, one fix per pass.
+    fn apply_fixes(
+        &self,
+        content: &str,
+        problems: &[LintProblem],
+        result: &mut FixResult,
+    ) -> Option<String> {
+        // Accepted edits keyed by start offset, mapping to their end offset
+        let mut accepted: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut edits: Vec<&TextEdit> = Vec::new();
+
+        for problem in problems {
+            let Some(fix) = &problem.fix else {
+                continue;
+            };
+            if !self.is_fixable(problem) || fix.edits.is_empty() {
+                continue;
+            }
+            if fix
+                .edits
+                .iter()
+                .any(|edit| Self::conflicts(&accepted, edit))
+            {
+                continue;
             }
 
-            // Try to fix problems in order (sorted by line number)
-            // This applies fixes top-to-bottom and avoids HashMap overhead
-            for problem in &problems {
-                if let Some(rule) = self.registry.get(&problem.rule) {
-                    if rule.is_fixable() {
-                        if let Some(fixed) = rule.fix(&current_content, problem) {
-                            current_content = fixed;
-                            result.fixes_applied += 1;
-                            *result
-                                .fixes_by_rule
-                                .entry(problem.rule.clone())
-                                .or_insert(0) += 1;
-                            made_progress = true;
-                            break; // Re-check all problems after each fix
-                        }
-                    }
-                }
+            for edit in &fix.edits {
+                accepted.insert(edit.range.start, edit.range.end);
+                edits.push(edit);
             }
+            result.record(&problem.rule);
         }
 
-        // Collect remaining unfixable problems
-        let context = LintContext::new(current_content.clone());
-        let remaining_problems = self.registry.check_all(&context);
-        for problem in remaining_problems {
-            if let Some(rule) = self.registry.get(&problem.rule) {
-                if !rule.is_fixable() {
-                    result.unfixable_problems.push(problem);
-                }
-            }
+        if !edits.is_empty() {
+            edits.sort_by_key(|edit| edit.range.start);
+            return Some(apply_edits(content, edits.into_iter()));
         }
 
-        if result.fixes_applied > 0 {
-            result.fixed_content = Some(current_content);
+        for problem in problems {
+            if problem.fix.is_some() || !self.is_fixable(problem) {
+                continue;
+            }
+            if let Some(fixed) = self
+                .registry
+                .get(&problem.rule)
+                .and_then(|rule| rule.fix(content, problem))
+                .filter(|fixed| fixed != content)
+            {
+                result.record(&problem.rule);
+                return Some(fixed);
+            }
         }
 
-        result
+        None
+    }
+
+    /// Check whether the rule that reported a problem can fix it
+    fn is_fixable(&self, problem: &LintProblem) -> bool {
+        self.registry
+            .get(&problem.rule)
+            .is_some_and(|rule| rule.is_fixable())
+    }
+
+    /// Check whether an edit overlaps one of the accepted edits. Edits starting
+    /// at the same offset conflict as well, since their order would be ambiguous.
+    fn conflicts(accepted: &BTreeMap<usize, usize>, edit: &TextEdit) -> bool {
+        let start = edit.range.start;
+        let before = accepted
+            .range(..=start)
+            .next_back()
+            .is_some_and(|(&other_start, &other_end)| other_start == start || other_end > start);
+        let after = accepted
+            .range(start + 1..)
+            .next()
+            .is_some_and(|(&other_start, _)| other_start < edit.range.end);
+
+        before || after
     }
 
     /// Check what fixes would be applied without actually applying them (dry-run)
@@ -119,9 +192,23 @@ impl<'a> Fixer<'a> {
     }
 }
 
+/// Apply every fix of a single rule to `content`, as `--fix` would
+#[cfg(test)]
+pub(crate) fn fix_with(rule: Box<dyn crate::rules::Rule>, content: &str) -> Option<String> {
+    let mut registry = RuleRegistry::new();
+    registry.register(rule);
+    Fixer::new(&registry)
+        .fix("test.yaml", content)
+        .fixed_content
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::problem::{Fix, LintLevel};
+    use crate::rules::RuleLevel;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[test]
     fn test_fix_trailing_spaces() {
@@ -196,4 +283,113 @@ mod tests {
                 .any(|p| p.rule == "key-duplicates")
         );
     }
+
+    #[test]
+    fn test_fixes_applied_in_one_pass() {
+        let registry = RuleRegistry::with_defaults();
+        let fixer = Fixer::new(&registry);
+
+        let content = "a: 1  \nb:   2\nc: 3\t";
+        let problems = registry.check_all(&LintContext::new(content.to_string()));
+        let mut result = FixResult::new("test.yaml".to_string());
+
+        let fixed = fixer.apply_fixes(content, &problems, &mut result);
+
+        assert_eq!(fixed, Some("a: 1\nb: 2\nc: 3\n".to_string()));
+        assert_eq!(result.fixes_by_rule.get("trailing-spaces"), Some(&2));
+        assert_eq!(result.fixes_by_rule.get("colons"), Some(&1));
+        assert_eq!(
+            result.fixes_by_rule.get("new-line-at-end-of-file"),
+            Some(&1)
+        );
+    }
+
+    /// Rule counting how often it checks a document
+    #[derive(Debug)]
+    struct CountingRule(Arc<AtomicUsize>);
+
+    impl crate::rules::Rule for CountingRule {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn check(&self, _context: &LintContext) -> Vec<LintProblem> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_fixes_without_overlap_take_one_pass() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(crate::rules::trailing_spaces::TrailingSpacesRule));
+        let checks = Arc::new(AtomicUsize::new(0));
+        registry.register(Box::new(CountingRule(Arc::clone(&checks))));
+        let fixer = Fixer::new(&registry);
+
+        let result = fixer.fix("test.yaml", "a: 1  \nb: 2\t\n");
+
+        assert_eq!(result.fixed_content, Some("a: 1\nb: 2\n".to_string()));
+        assert_eq!(result.fixes_applied, 2);
+        // The initial lint, and the one finding nothing left after the pass
+        assert_eq!(checks.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_overlapping_fixes_deferred() {
+        let registry = RuleRegistry::with_defaults();
+        let fixer = Fixer::new(&registry);
+
+        let content = "key: value   \n";
+        let problem = |range: std::ops::Range<usize>| {
+            LintProblem::new(
+                1,
+                11,
+                "trailing spaces",
+                "trailing-spaces",
+                LintLevel::Error,
+            )
+            .with_fix(Fix::new(vec![TextEdit::delete(range)]))
+        };
+        let problems = vec![problem(10..13), problem(11..12), problem(10..10)];
+        let mut result = FixResult::new("test.yaml".to_string());
+
+        let fixed = fixer.apply_fixes(content, &problems, &mut result);
+
+        assert_eq!(fixed, Some("key: value\n".to_string()));
+        assert_eq!(result.fixes_applied, 1);
+    }
+
+    #[test]
+    fn test_whole_file_fix_after_edits() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(crate::rules::trailing_spaces::TrailingSpacesRule));
+        registry.register(Box::new(
+            crate::rules::indentation::IndentationRule::with_spaces(2),
+        ));
+        let fixer = Fixer::new(&registry);
+
+        let result = fixer.fix("test.yaml", "a:\n   b: 1  \n");
+
+        assert_eq!(result.fixed_content, Some("a:\n  b: 1\n".to_string()));
+        assert_eq!(result.fixes_by_rule.get("trailing-spaces"), Some(&1));
+        assert_eq!(result.fixes_by_rule.get("indentation"), Some(&1));
+        assert!(!result.has_unfixable());
+    }
+
+    #[test]
+    fn test_remaining_problems_of_fixable_rules_reported() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(
+            crate::rules::document_start::DocumentStartRule::forbidden(),
+        ));
+        registry.set_level("document-start", RuleLevel::Error);
+        let fixer = Fixer::new(&registry);
+
+        let result = fixer.fix("test.yaml", "---\na: 1\n---\nb: 2\n");
+
+        assert_eq!(result.fixed_content, Some("a: 1\n---\nb: 2\n".to_string()));
+        assert_eq!(result.unfixable_problems.len(), 1);
+        assert_eq!(result.unfixable_problems[0].line, 2);
+    }
 }
@@ -0,0 +1,248 @@
+//! Starter configs inferred from existing files
+//!
+//! Conventions the files already follow, such as the indentation width, are
+//! written into the config, and rules most files break are set to
+//! `warning` so a repository can adopt the config without fixing every file
+//! first.
+//!
+//! The quote style is not inferred: only yamllint's `quoted-strings` rule
+//! configures it, and that rule is not implemented here, so there is no
+//! setting to write it to.
+
+use crate::Result;
+use crate::config::{BUILTIN_RULES, Config};
+use crate::linter::Linter;
+use crate::rules::indentation::detected_indent_size;
+use crate::rules::{LintContext, RuleLevel};
+use std::collections::HashMap;
+
+/// Share of lines the inferred `line-length` max allows, in percent
+const LINE_LENGTH_PERCENTILE: usize = 99;
+
+/// Smallest `line-length` max ever inferred, the default
+const MIN_LINE_LENGTH: usize = 80;
+
+/// Rules breaking in more than one in this many files are set to `warning`
+const MANY_VIOLATIONS: usize = 10;
+
+/// Conventions observed in a set of YAML files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conventions {
+    /// Number of files observed
+    pub files: usize,
+    /// Most common indentation width, `None` if no file indents anything
+    pub indent_size: Option<usize>,
+    /// Line length that nearly all lines stay within, rounded up to a
+    /// multiple of 10
+    pub line_length: usize,
+    /// Number of files whose first document starts with `---`
+    pub document_starts: usize,
+}
+
+impl Conventions {
+    /// Observe the conventions of files with the given contents
+    pub fn observe(contents: &[String]) -> Self {
+        let mut indent_sizes: HashMap<usize, usize> = HashMap::new();
+        let mut line_lengths = Vec::new();
+        let mut document_starts = 0;
+
+        for content in contents {
+            let context = LintContext::new(content.clone());
+            if let Some(size) = detected_indent_size(&context) {
+                *indent_sizes.entry(size).or_default() += 1;
+            }
+            line_lengths.extend(
+                context
+                    .lines
+                    .iter()
+                    .map(String::len)
+                    .filter(|length| *length > 0),
+            );
+
+            // Comments and directives may come before the marker
+            let first_line =
+                context.lines.iter().map(|line| line.trim()).find(|line| {
+                    !line.is_empty() && !line.starts_with('#') && !line.starts_with('%')
+                });
+            if first_line.is_some_and(|line| line == "---" || line.starts_with("--- ")) {
+                document_starts += 1;
+            }
+        }
+
+        // Most common width, the smaller one on ties
+        let indent_size = indent_sizes
+            .into_iter()
+            .max_by_key(|(size, files)| (*files, std::cmp::Reverse(*size)))
+            .map(|(size, _)| size);
+
+        line_lengths.sort_unstable();
+        let line_length = match line_lengths.len() {
+            0 => MIN_LINE_LENGTH,
+            count => {
+                let index = (count * LINE_LENGTH_PERCENTILE).div_ceil(100) - 1;
+                line_lengths[index].div_ceil(10) * 10
+            }
+        }
+        .max(MIN_LINE_LENGTH);
+
+        Self {
+            files: contents.len(),
+            indent_size,
+            line_length,
+            document_starts,
+        }
+    }
+
+    /// Whether most files start with `---`
+    pub fn uses_document_start(&self) -> bool {
+        self.document_starts * 2 > self.files
+    }
+}
+
+/// Config file content matching the conventions of files with the given
+/// contents, with the rules many of them break set to `warning`
+pub fn starter_config(contents: &[String]) -> Result<String> {
+    let conventions = Conventions::observe(contents);
+
+    // Count the files each rule finds problems in with the conventions
+    let strict = Config::load_from_str(&config_yaml(&conventions, &HashMap::new()))?;
+    let linter = Linter::new(strict)?;
+    let mut failing: HashMap<String, usize> = HashMap::new();
+    for content in contents {
+        let mut rules: Vec<String> = linter
+            .lint_string(content)?
+            .into_iter()
+            .map(|problem| problem.rule)
+            .collect();
+        rules.sort();
+        rules.dedup();
+        for rule in rules {
+            *failing.entry(rule).or_default() += 1;
+        }
+    }
+
+    let yaml = config_yaml(&conventions, &failing);
+    // The generated config must load
+    Config::load_from_str(&yaml)?;
+    Ok(yaml)
+}
+
+/// Config extending the default preset with the conventions, setting the
+/// rules that fail in many files, counted in `failing`, to `warning`
+fn config_yaml(conventions: &Conventions, failing: &HashMap<String, usize>) -> String {
+    let defaults = Config::with_default_preset();
+    let mut rules = String::new();
+
+    for rule in BUILTIN_RULES {
+        let mut options = Vec::new();
+        match rule {
+            "indentation" => {
+                if let Some(size) = conventions.indent_size {
+                    options.push(format!("spaces: {}", size));
+                }
+            }
+            "line-length" if conventions.line_length != MIN_LINE_LENGTH => {
+                options.push(format!("max: {}", conventions.line_length));
+            }
+            "document-start" if conventions.uses_document_start() => {
+                options.push("present: true".to_string());
+            }
+            _ => {}
+        }
+
+        let default_level = match (rule, options.is_empty()) {
+            // The default preset disables document-start
+            ("document-start", false) => RuleLevel::Error,
+            _ => defaults.get_rule_level(rule).unwrap_or(RuleLevel::Disable),
+        };
+        let files = failing.get(rule).copied().unwrap_or(0);
+        let many = files > 0 && files * MANY_VIOLATIONS > conventions.files;
+        let level = match default_level {
+            RuleLevel::Error if many => RuleLevel::Warning,
+            level => level,
+        };
+        if options.is_empty() && level == default_level {
+            continue;
+        }
+
+        let comment = if many {
+            format!("  # problems in {} of {} files", files, conventions.files)
+        } else {
+            String::new()
+        };
+        if options.is_empty() {
+            rules.push_str(&format!("  {}: {}{}\n", rule, level, comment));
+        } else {
+            rules.push_str(&format!("  {}:{}\n    level: {}\n", rule, comment, level));
+            for option in options {
+                rules.push_str(&format!("    {}\n", option));
+            }
+        }
+    }
+
+    let mut yaml = format!(
+        "# Generated by `yaml-lint init` from {} file(s)\nextends: default\n",
+        conventions.files
+    );
+    if !rules.is_empty() {
+        yaml.push_str("\nrules:\n");
+        yaml.push_str(&rules);
+    }
+    yaml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(files: &[&str]) -> Vec<String> {
+        files.iter().map(|file| file.to_string()).collect()
+    }
+
+    #[test]
+    fn test_observe_conventions() {
+        let long_line = format!("key: {}\n", "x".repeat(95));
+        let conventions = Conventions::observe(&contents(&[
+            "---\nroot:\n    child: 1\n",
+            "# comment\n---\nlist:\n    - a\n",
+            "flat: 1\n",
+            &long_line,
+        ]));
+
+        assert_eq!(conventions.files, 4);
+        assert_eq!(conventions.indent_size, Some(4));
+        assert_eq!(conventions.line_length, 100);
+        assert_eq!(conventions.document_starts, 2);
+        assert!(!conventions.uses_document_start());
+    }
+
+    #[test]
+    fn test_starter_config() {
+        let yaml = starter_config(&contents(&[
+            "---\nroot:\n    child: yes\n",
+            "---\nkey:  value\nother:\n    child: 1\n",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            yaml,
+            "# Generated by `yaml-lint init` from 2 file(s)\n\
+             extends: default\n\
+             \n\
+             rules:\n  \
+               document-start:\n    \
+                 level: error\n    \
+                 present: true\n  \
+               colons: warning  # problems in 1 of 2 files\n  \
+               indentation:\n    \
+                 level: error\n    \
+                 spaces: 4\n"
+        );
+
+        // Files following the default preset need no rules
+        assert_eq!(
+            starter_config(&contents(&["key: value\n"])).unwrap(),
+            "# Generated by `yaml-lint init` from 1 file(s)\nextends: default\n"
+        );
+    }
+}
@@ -3,19 +3,26 @@
 //! Core YAML linting engine providing the fundamental types and traits
 //! for building YAML linters.
 
+pub mod baseline;
+pub mod compat;
 pub mod config;
+pub mod directives;
+pub mod discovery;
 pub mod fixer;
+pub mod init;
 pub mod linter;
 pub mod output;
 pub mod problem;
 pub mod rules;
 
 // Re-export main types for convenience
+pub use baseline::Baseline;
 pub use config::Config;
+pub use discovery::ConfigResolver;
 pub use fixer::{FixResult, Fixer};
 pub use linter::Linter;
-pub use problem::{LintLevel, LintProblem};
-pub use rules::{Rule, RuleRegistry};
+pub use problem::{Fix, LintLevel, LintProblem, TextEdit};
+pub use rules::{Rule, RuleMetadata, RuleRegistry};
 
 /// Result type for lint operations
 pub type Result<T> = std::result::Result<T, LintError>;
@@ -34,4 +41,7 @@ pub enum LintError {
 
     #[error("Unknown rule: {0}")]
     UnknownRule(String),
+
+    #[error("Invalid baseline: {0}")]
+    BaselineError(String),
 }
@@ -4,32 +4,70 @@ use crate::Result;
 use crate::config::Config;
 use crate::problem::LintProblem;
 use crate::rules::{LintContext, RuleRegistry};
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Main linter that orchestrates the linting process
 #[derive(Debug)]
 pub struct Linter {
     config: Config,
-    registry: RuleRegistry,
+    registry: Arc<RuleRegistry>,
+    /// Registries for files matching `overrides`, keyed by the indexes of the
+    /// overrides that apply
+    override_registries: Mutex<HashMap<Vec<usize>, Arc<RuleRegistry>>>,
 }
 
 impl Linter {
     /// Create a new linter with the given configuration
-    pub fn new(config: Config) -> Self {
-        let registry = config.create_registry();
+    ///
+    /// Fails if the configuration names a rule that does not exist.
+    pub fn new(config: Config) -> Result<Self> {
+        let registry = Arc::new(config.create_registry()?);
 
-        Self { config, registry }
+        Ok(Self {
+            config,
+            registry,
+            override_registries: Mutex::new(HashMap::new()),
+        })
     }
 
     /// Create a linter with default configuration
     pub fn with_defaults() -> Self {
-        Self::new(Config::default())
+        Self::new(Config::default()).expect("the default preset only names built-in rules")
     }
 
     /// Lint a file at the given path
     pub fn lint_file(&self, path: &Path) -> Result<Vec<LintProblem>> {
         let content = std::fs::read_to_string(path)?;
-        self.lint_string(&content)
+        self.lint_content(path, &content)
+    }
+
+    /// Lint the already-read content of the file at `path`, applying the
+    /// config overrides that match it
+    pub fn lint_content(&self, path: &Path, content: &str) -> Result<Vec<LintProblem>> {
+        let context = LintContext::new(content.to_string());
+        Ok(self.registry_for_path(path)?.check_all(&context))
+    }
+
+    /// Rule registry for the file at `path`, with matching overrides applied
+    pub fn registry_for_path(&self, path: &Path) -> Result<Arc<RuleRegistry>> {
+        let matched = self.config.matching_overrides(path);
+        if matched.is_empty() {
+            return Ok(Arc::clone(&self.registry));
+        }
+
+        let mut registries = self
+            .override_registries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(registry) = registries.get(&matched) {
+            return Ok(Arc::clone(registry));
+        }
+
+        let registry = Arc::new(self.config.with_overrides(&matched).create_registry()?);
+        registries.insert(matched, Arc::clone(&registry));
+        Ok(registry)
     }
 
     /// Lint a YAML string
@@ -79,9 +117,45 @@ mod tests {
             crate::config::RuleConfig::Level(crate::rules::RuleLevel::Disable),
         );
 
-        let linter = Linter::new(config);
+        let linter = Linter::new(config).unwrap();
         let yaml = "key: value   \n";
         let problems = linter.lint_string(yaml).unwrap();
         assert!(problems.is_empty()); // Rule is disabled
     }
+
+    #[test]
+    fn test_lint_content_applies_overrides() {
+        let config = Config::load_from_str(
+            r#"
+extends: default
+overrides:
+  - files: ["ansible/**"]
+    rules:
+      truthy: disable
+  - files: ["ansible/legacy/*.yaml"]
+    rules:
+      truthy: error
+"#,
+        )
+        .unwrap();
+        let linter = Linter::new(config).unwrap();
+        let yaml = "enabled: yes\n";
+
+        let levels = |path: &str| -> Vec<_> {
+            linter
+                .lint_content(Path::new(path), yaml)
+                .unwrap()
+                .iter()
+                .map(|problem| problem.level)
+                .collect()
+        };
+
+        assert_eq!(levels("k8s/app.yaml"), vec![crate::LintLevel::Warning]);
+        assert!(levels("ansible/site.yaml").is_empty());
+        assert!(levels("./ansible/roles/main.yaml").is_empty());
+        assert_eq!(
+            levels("ansible/legacy/old.yaml"),
+            vec![crate::LintLevel::Error]
+        );
+    }
 }
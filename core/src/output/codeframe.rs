@@ -0,0 +1,301 @@
+//! Code-frame output formatter with source snippets
+
+use crate::output::OutputFormatter;
+use crate::problem::{LintLevel, LintProblem};
+use colored::{ColoredString, Colorize};
+
+/// Maximum number of source lines shown for a single problem
+const MAX_FRAME_LINES: usize = 4;
+
+/// Formatter printing each problem with the offending source lines and a
+/// caret underline, in the style of rustc diagnostics
+pub struct CodeFrameFormatter {
+    colored: bool,
+}
+
+impl CodeFrameFormatter {
+    /// Create a formatter without ANSI colors
+    pub fn plain() -> Self {
+        Self { colored: false }
+    }
+
+    /// Create a formatter using ANSI colors
+    pub fn colored() -> Self {
+        Self { colored: true }
+    }
+
+    /// Apply a color to `text` if this formatter is colored
+    fn paint(&self, text: &str, style: impl Fn(&str) -> ColoredString) -> String {
+        if self.colored {
+            style(text).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Color used for the level and the underline of a problem
+    fn paint_level(&self, text: &str, level: LintLevel) -> String {
+        match level {
+            LintLevel::Error => self.paint(text, |t| t.red().bold()),
+            LintLevel::Warning => self.paint(text, |t| t.yellow().bold()),
+        }
+    }
+
+    /// Append the frame of a single problem to `output`
+    fn format_frame(
+        &self,
+        output: &mut String,
+        problem: &LintProblem,
+        file_path: &str,
+        lines: &[&str],
+        gutter: usize,
+    ) {
+        let blank_gutter = format!("{} |", " ".repeat(gutter));
+        let blank_gutter = self.paint(&blank_gutter, |t| t.blue().bold());
+
+        // error[trailing-spaces]: trailing spaces
+        let header = format!("{}[{}]", problem.level, problem.rule);
+        output.push_str(&self.paint_level(&header, problem.level));
+        output.push_str(&self.paint(&format!(": {}", problem.message), |t| t.bold()));
+        output.push('\n');
+
+        //  --> file.yaml:12:3
+        output.push_str(&" ".repeat(gutter));
+        output.push_str(&self.paint("--> ", |t| t.blue().bold()));
+        output.push_str(&format!(
+            "{}:{}:{}\n",
+            file_path, problem.line, problem.column
+        ));
+
+        let end_line = problem.end_line.unwrap_or(problem.line).max(problem.line);
+        let shown_end = end_line.min(problem.line + MAX_FRAME_LINES - 1);
+        if lines.get(problem.line - 1).is_none() {
+            output.push('\n');
+            return;
+        }
+
+        output.push_str(&blank_gutter);
+        output.push('\n');
+
+        for line_num in problem.line..=shown_end {
+            let Some(text) = lines.get(line_num - 1) else {
+                break;
+            };
+
+            // Underline from the problem start (or line start) to its end
+            // (or line end), with at least one caret
+            let start = if line_num == problem.line {
+                problem.column
+            } else {
+                1
+            };
+            let end = match problem.end_column {
+                Some(end_column) if line_num == end_line => end_column,
+                _ if line_num == problem.line && problem.end_column.is_none() => start + 1,
+                _ => text.len() + 1,
+            };
+            let underline = Self::underline(text, start, end);
+
+            let number = format!("{:>width$} |", line_num, width = gutter);
+            output.push_str(&self.paint(&number, |t| t.blue().bold()));
+            if !text.is_empty() {
+                output.push(' ');
+                output.push_str(text);
+            }
+            output.push('\n');
+
+            output.push_str(&blank_gutter);
+            output.push(' ');
+            output.push_str(&underline.0);
+            output.push_str(&self.paint_level(&underline.1, problem.level));
+            output.push('\n');
+        }
+
+        if shown_end < end_line {
+            output
+                .push_str(&self.paint(&format!("{}...", " ".repeat(gutter)), |t| t.blue().bold()));
+            output.push('\n');
+        }
+
+        output.push('\n');
+    }
+
+    /// Build the padding and carets underlining byte columns `start..end`
+    /// (1-indexed) of `text`. Tabs are kept in the padding so the carets line
+    /// up with the source however the terminal renders them.
+    fn underline(text: &str, start: usize, end: usize) -> (String, String) {
+        let start_idx = (start.max(1) - 1).min(text.len());
+        let end_idx = (end.max(1) - 1).clamp(start_idx, text.len());
+
+        let padding: String = text
+            .get(..start_idx)
+            .unwrap_or(text)
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = text
+            .get(start_idx..end_idx)
+            .map_or(0, |marked| marked.chars().count());
+
+        (padding, "^".repeat(width.max(1)))
+    }
+}
+
+impl OutputFormatter for CodeFrameFormatter {
+    fn format_problems(&self, problems: &[LintProblem], file_path: &str) -> String {
+        // Without the source only the headers and locations can be shown
+        self.format_problems_with_source(problems, file_path, "")
+    }
+
+    fn format_problems_with_source(
+        &self,
+        problems: &[LintProblem],
+        file_path: &str,
+        content: &str,
+    ) -> String {
+        if problems.is_empty() {
+            return String::new();
+        }
+
+        let lines: Vec<&str> = content.lines().collect();
+        let gutter = problems
+            .iter()
+            .map(|problem| problem.end_line.unwrap_or(problem.line).max(problem.line))
+            .max()
+            .map_or(1, |line| line.to_string().len());
+
+        let mut output = String::new();
+        for problem in problems {
+            self.format_frame(&mut output, problem, file_path, &lines, gutter);
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::LintContext;
+
+    #[test]
+    fn test_format_with_source() {
+        let content = "key: value\nname: test   \n";
+        let context = LintContext::new(content.to_string());
+        let problems = vec![
+            LintProblem::new(
+                2,
+                11,
+                "trailing spaces",
+                "trailing-spaces",
+                LintLevel::Error,
+            )
+            .with_range(&context, 2, 14),
+        ];
+
+        let output = CodeFrameFormatter::plain().format_problems_with_source(
+            &problems,
+            "test.yaml",
+            content,
+        );
+
+        assert_eq!(
+            output,
+            "error[trailing-spaces]: trailing spaces\n \
+             --> test.yaml:2:11\n  \
+             |\n\
+             2 | name: test   \n  \
+             |           ^^^\n\n"
+        );
+    }
+
+    #[test]
+    fn test_format_without_range_uses_single_caret() {
+        let content = "key: yes\n";
+        let problems = vec![LintProblem::new(
+            1,
+            6,
+            "truthy value",
+            "truthy",
+            LintLevel::Warning,
+        )];
+
+        let output = CodeFrameFormatter::plain().format_problems_with_source(
+            &problems,
+            "test.yaml",
+            content,
+        );
+
+        assert!(output.starts_with("warning[truthy]: truthy value\n"));
+        assert!(output.contains("1 | key: yes\n  |      ^\n"));
+    }
+
+    #[test]
+    fn test_format_multi_line_range() {
+        let content = "a: 1\n\n\n\n\n\n\nb: 2\n";
+        let context = LintContext::new(content.to_string());
+        let problems = vec![
+            LintProblem::new(
+                2,
+                1,
+                "too many blank lines",
+                "empty-lines",
+                LintLevel::Error,
+            )
+            .with_range(&context, 7, 1),
+        ];
+
+        let output = CodeFrameFormatter::plain().format_problems_with_source(
+            &problems,
+            "test.yaml",
+            content,
+        );
+
+        assert!(output.contains("2 |\n  | ^\n"));
+        assert!(output.contains("5 |\n  | ^\n ...\n"));
+        assert!(!output.contains("6 |"));
+    }
+
+    #[test]
+    fn test_format_keeps_tabs_in_underline() {
+        let content = "\tkey:  value\n";
+        let context = LintContext::new(content.to_string());
+        let problems = vec![
+            LintProblem::new(1, 6, "too many spaces", "colons", LintLevel::Error)
+                .with_range(&context, 1, 8),
+        ];
+
+        let output = CodeFrameFormatter::plain().format_problems_with_source(
+            &problems,
+            "test.yaml",
+            content,
+        );
+
+        assert!(output.contains("  | \t    ^^\n"));
+    }
+
+    #[test]
+    fn test_format_without_source() {
+        let problems = vec![LintProblem::new(
+            3,
+            1,
+            "found duplicate key",
+            "key-duplicates",
+            LintLevel::Error,
+        )];
+
+        let output = CodeFrameFormatter::plain().format_problems(&problems, "test.yaml");
+
+        assert_eq!(
+            output,
+            "error[key-duplicates]: found duplicate key\n --> test.yaml:3:1\n\n"
+        );
+    }
+
+    #[test]
+    fn test_format_no_problems() {
+        let output =
+            CodeFrameFormatter::colored().format_problems_with_source(&[], "test.yaml", "");
+        assert!(output.is_empty());
+    }
+}
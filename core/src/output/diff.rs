@@ -0,0 +1,101 @@
+//! Unified diff output for auto-fixes
+
+use colored::Colorize;
+use similar::TextDiff;
+use std::path::Path;
+
+/// Number of unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Format a unified diff between the original and fixed content of a file.
+/// Returns an empty string if the contents are identical.
+pub fn unified_diff(file_path: &str, original: &str, fixed: &str) -> String {
+    if original == fixed {
+        return String::new();
+    }
+
+    // Relative paths get git-style prefixes so the diff applies with `patch -p1`
+    let (old_header, new_header) = if Path::new(file_path).is_absolute() {
+        (file_path.to_string(), file_path.to_string())
+    } else {
+        (format!("a/{}", file_path), format!("b/{}", file_path))
+    };
+
+    TextDiff::from_lines(original, fixed)
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header(&old_header, &new_header)
+        .to_string()
+}
+
+/// Colorize a unified diff: file headers bold, hunk headers cyan, removed
+/// lines red and added lines green
+pub fn colorize_diff(diff: &str) -> String {
+    let mut output = String::with_capacity(diff.len());
+
+    for line in diff.split_inclusive('\n') {
+        let text = line.trim_end_matches('\n');
+        let colored = if text.starts_with("---") || text.starts_with("+++") {
+            text.bold()
+        } else if text.starts_with("@@") {
+            text.cyan()
+        } else if text.starts_with('-') {
+            text.red()
+        } else if text.starts_with('+') {
+            text.green()
+        } else {
+            output.push_str(line);
+            continue;
+        };
+
+        output.push_str(&colored.to_string());
+        output.push_str(&line[text.len()..]);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        let diff = unified_diff("config.yaml", "a: 1  \nb: 2\n", "a: 1\nb: 2\n");
+
+        assert_eq!(
+            diff,
+            "--- a/config.yaml\n+++ b/config.yaml\n@@ -1,2 +1,2 @@\n-a: 1  \n+a: 1\n b: 2\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_missing_newline() {
+        let diff = unified_diff("config.yaml", "a: 1", "a: 1\n");
+
+        assert!(diff.contains("-a: 1\n\\ No newline at end of file\n+a: 1\n"));
+    }
+
+    #[test]
+    fn test_unified_diff_absolute_path() {
+        let diff = unified_diff("/tmp/config.yaml", "a:  1\n", "a: 1\n");
+
+        assert!(diff.starts_with("--- /tmp/config.yaml\n+++ /tmp/config.yaml\n"));
+    }
+
+    #[test]
+    fn test_unified_diff_identical() {
+        assert!(unified_diff("config.yaml", "a: 1\n", "a: 1\n").is_empty());
+    }
+
+    #[test]
+    fn test_colorize_diff_keeps_text() {
+        let diff = unified_diff("config.yaml", "a:  1\n", "a: 1\n");
+        let colored = colorize_diff(&diff);
+
+        assert!(colored.contains("a/config.yaml"));
+        assert!(colored.contains("-a:  1"));
+        assert!(colored.contains("+a: 1"));
+        assert_eq!(colored.lines().count(), diff.lines().count());
+    }
+}
@@ -2,11 +2,15 @@
 
 use crate::problem::LintProblem;
 
+pub mod codeframe;
 pub mod colored;
+pub mod diff;
 pub mod parsable;
 pub mod standard;
 
+pub use codeframe::CodeFrameFormatter;
 pub use colored::ColoredFormatter;
+pub use diff::{colorize_diff, unified_diff};
 pub use parsable::ParsableFormatter;
 pub use standard::StandardFormatter;
 
@@ -14,6 +18,17 @@ pub use standard::StandardFormatter;
 pub trait OutputFormatter {
     /// Format a list of problems for a given file
     fn format_problems(&self, problems: &[LintProblem], file_path: &str) -> String;
+
+    /// Format a list of problems for a given file, with access to its content
+    /// for formatters that show source snippets
+    fn format_problems_with_source(
+        &self,
+        problems: &[LintProblem],
+        file_path: &str,
+        _content: &str,
+    ) -> String {
+        self.format_problems(problems, file_path)
+    }
 }
 
 /// Output format types
@@ -25,6 +40,10 @@ pub enum OutputFormat {
     Colored,
     /// Machine-parsable format (future)
     Parsable,
+    /// Source snippets with the problem underlined
+    CodeFrame,
+    /// Source snippets with the problem underlined, using colors
+    ColoredCodeFrame,
 }
 
 impl OutputFormat {
@@ -34,6 +53,8 @@ impl OutputFormat {
             OutputFormat::Standard => Box::new(StandardFormatter),
             OutputFormat::Colored => Box::new(ColoredFormatter),
             OutputFormat::Parsable => Box::new(ParsableFormatter),
+            OutputFormat::CodeFrame => Box::new(CodeFrameFormatter::plain()),
+            OutputFormat::ColoredCodeFrame => Box::new(CodeFrameFormatter::colored()),
         }
     }
 }
@@ -46,6 +67,7 @@ impl std::str::FromStr for OutputFormat {
             "standard" => Ok(OutputFormat::Standard),
             "colored" => Ok(OutputFormat::Colored),
             "parsable" => Ok(OutputFormat::Parsable),
+            "codeframe" => Ok(OutputFormat::CodeFrame),
             _ => Err(format!("Unknown output format: {}", s)),
         }
     }
@@ -1,6 +1,8 @@
 //! Lint problem types and levels
 
+use crate::rules::LintContext;
 use std::cmp::Ordering;
+use std::ops::Range;
 
 /// Severity level of a lint problem
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -20,6 +22,70 @@ impl std::fmt::Display for LintLevel {
     }
 }
 
+/// A single text replacement, expressed as a byte range of the linted content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte range of the original content to replace
+    pub range: Range<usize>,
+    /// Text inserted in place of the range
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Create an edit replacing `range` with `replacement`
+    pub fn new(range: Range<usize>, replacement: impl Into<String>) -> Self {
+        Self {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Create an edit deleting `range`
+    pub fn delete(range: Range<usize>) -> Self {
+        Self::new(range, "")
+    }
+
+    /// Create an edit inserting `text` at `offset`
+    pub fn insert(offset: usize, text: impl Into<String>) -> Self {
+        Self::new(offset..offset, text)
+    }
+}
+
+/// Edits that fix a single problem; they are always applied together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Non-overlapping edits, sorted by position
+    pub edits: Vec<TextEdit>,
+}
+
+impl Fix {
+    /// Create a fix from a set of non-overlapping edits
+    pub fn new(mut edits: Vec<TextEdit>) -> Self {
+        edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+        Self { edits }
+    }
+
+    /// Apply the edits of this fix to `content`
+    pub fn apply(&self, content: &str) -> String {
+        apply_edits(content, self.edits.iter())
+    }
+}
+
+/// Apply sorted, non-overlapping edits to `content` in a single pass
+pub(crate) fn apply_edits<'a>(content: &str, edits: impl Iterator<Item = &'a TextEdit>) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for edit in edits {
+        result.push_str(&content[last..edit.range.start]);
+        result.push_str(&edit.replacement);
+        last = edit.range.end;
+    }
+    result.push_str(&content[last..]);
+
+    result
+}
+
 /// A lint problem found in a YAML file
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LintProblem {
@@ -33,6 +99,14 @@ pub struct LintProblem {
     pub rule: String,
     /// Severity level
     pub level: LintLevel,
+    /// End line number (1-indexed), if the problem covers a range
+    pub end_line: Option<usize>,
+    /// End column number (1-indexed, exclusive), if the problem covers a range
+    pub end_column: Option<usize>,
+    /// Byte range of the problem in the linted content
+    pub span: Option<Range<usize>>,
+    /// Edits that fix this problem, if the rule can fix it
+    pub fix: Option<Fix>,
 }
 
 impl LintProblem {
@@ -50,8 +124,35 @@ impl LintProblem {
             message: message.into(),
             rule: rule.into(),
             level,
+            end_line: None,
+            end_column: None,
+            span: None,
+            fix: None,
         }
     }
+
+    /// Set where the problem ends, as a 1-indexed line and exclusive column,
+    /// along with the matching byte span of the linted content
+    pub fn with_range(mut self, context: &LintContext, end_line: usize, end_column: usize) -> Self {
+        let offset = |line: usize, column: usize| {
+            context
+                .offset(line.saturating_sub(1), column.saturating_sub(1))
+                .min(context.content.len())
+        };
+        let start = offset(self.line, self.column);
+        let end = offset(end_line, end_column).max(start);
+
+        self.end_line = Some(end_line);
+        self.end_column = Some(end_column);
+        self.span = Some(start..end);
+        self
+    }
+
+    /// Attach a fix to this problem
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
 }
 
 impl PartialOrd for LintProblem {
@@ -96,4 +197,46 @@ mod tests {
 
         assert!(error < warning);
     }
+
+    #[test]
+    fn test_fix_apply() {
+        let fix = Fix::new(vec![
+            TextEdit::insert(11, "\n"),
+            TextEdit::delete(3..4),
+            TextEdit::new(6..11, "true"),
+        ]);
+
+        assert_eq!(fix.apply("key : value"), "key: true\n");
+    }
+
+    #[test]
+    fn test_with_range() {
+        let context = LintContext::new("a: 1\nkey: value   \n".to_string());
+        let problem = LintProblem::new(2, 11, "trailing spaces", "rule", LintLevel::Error)
+            .with_range(&context, 2, 14);
+
+        assert_eq!(problem.end_line, Some(2));
+        assert_eq!(problem.end_column, Some(14));
+        assert_eq!(problem.span, Some(15..18));
+        assert_eq!(&context.content[15..18], "   ");
+    }
+
+    #[test]
+    fn test_ordering_ignores_range() {
+        let context = LintContext::new("key: value\n".to_string());
+        let short = LintProblem::new(1, 1, "test", "rule", LintLevel::Error);
+        let long =
+            LintProblem::new(1, 1, "test", "rule", LintLevel::Error).with_range(&context, 1, 11);
+
+        assert_eq!(short.cmp(&long), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_ordering_ignores_fix() {
+        let plain = LintProblem::new(1, 1, "test", "rule", LintLevel::Error);
+        let fixed = LintProblem::new(1, 1, "test", "rule", LintLevel::Error)
+            .with_fix(Fix::new(vec![TextEdit::delete(0..1)]));
+
+        assert_eq!(plain.cmp(&fixed), Ordering::Equal);
+    }
 }
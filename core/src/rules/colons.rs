@@ -1,7 +1,8 @@
 //! Colons rule - validates spacing around colons in mappings
 
-use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::problem::{Fix, LintLevel, LintProblem, TextEdit};
+use crate::rules::indentation::{block_scalar_content, scalar_continuation_lines};
+use crate::rules::{LintContext, OptionType, Rule, RuleLevel, RuleMetadata, RuleOption};
 
 /// Rule that checks spacing around colons in key-value mappings
 #[derive(Debug)]
@@ -28,6 +29,47 @@ impl ColonsRule {
             max_spaces_after: max_after,
         }
     }
+
+    /// Check if a line is a comment, empty, a document marker or a directive
+    fn is_skipped_line(line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed.starts_with("---")
+            || trimmed.starts_with("...")
+            || trimmed.starts_with('%')
+    }
+
+    /// Find the first colon that is part of a key-value pair (not in strings)
+    ///
+    /// Simple approach: look for colons followed by space, end of line or comment
+    fn find_mapping_colon(line: &str) -> Option<usize> {
+        let mut in_single_quote = false;
+        let mut in_double_quote = false;
+        let mut escaped = false;
+
+        for (col_idx, ch) in line.char_indices() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+
+            match ch {
+                '\\' if in_double_quote => escaped = true,
+                '\'' if !in_double_quote => in_single_quote = !in_single_quote,
+                '"' if !in_single_quote => in_double_quote = !in_double_quote,
+                ':' if !in_single_quote && !in_double_quote => {
+                    let next_char = line[col_idx + 1..].chars().next();
+                    if matches!(next_char, None | Some(' ') | Some('#')) {
+                        return Some(col_idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
 }
 
 impl Default for ColonsRule {
@@ -41,102 +83,134 @@ impl Rule for ColonsRule {
         "colons"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "colons",
+            description: "Limits the spaces before and after the colons of mappings.",
+            rationale: "Consistent spacing around colons makes mappings easier to scan.",
+            fixable: true,
+            options: &[
+                RuleOption {
+                    name: "max-spaces-before",
+                    value_type: OptionType::Integer,
+                    default: Some("0"),
+                    description: "Maximum number of spaces before a colon, or -1 for no limit",
+                },
+                RuleOption {
+                    name: "max-spaces-after",
+                    value_type: OptionType::Integer,
+                    default: Some("1"),
+                    description: "Maximum number of spaces after a colon, or -1 for no limit",
+                },
+            ],
+            bad_example: "key :  value\n",
+            good_example: "key: value\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
+        let in_block_scalar = block_scalar_content(&context.lines);
+        let in_scalar = scalar_continuation_lines(&context.lines);
 
         for (line_idx, line) in context.lines.iter().enumerate() {
-            // Skip comment lines and empty lines
-            let trimmed = line.trim();
-            if trimmed.is_empty() || trimmed.starts_with('#') {
+            if in_block_scalar[line_idx] || in_scalar[line_idx] || Self::is_skipped_line(line) {
                 continue;
             }
 
-            // Skip document markers and directives
-            if trimmed.starts_with("---") || trimmed.starts_with("...") || trimmed.starts_with('%')
-            {
+            // Only check first colon in line for simplicity
+            let Some(col_idx) = Self::find_mapping_colon(line) else {
                 continue;
+            };
+
+            let rest = &line[col_idx + 1..];
+            let next_char = rest.chars().next();
+
+            // Check spaces before colon
+            let spaces_before = line[..col_idx]
+                .chars()
+                .rev()
+                .take_while(|&c| c == ' ')
+                .count();
+
+            if spaces_before > self.max_spaces_before {
+                let problem = LintProblem::new(
+                    line_idx + 1,
+                    col_idx + 1,
+                    format!(
+                        "too many spaces before colon ({} > {})",
+                        spaces_before, self.max_spaces_before
+                    ),
+                    self.name(),
+                    LintLevel::Error,
+                )
+                .with_range(context, line_idx + 1, col_idx + 2);
+
+                // Never remove the indentation of a line starting with a colon
+                let key_start = col_idx - spaces_before + self.max_spaces_before;
+                problems.push(if line[..col_idx].trim().is_empty() {
+                    problem
+                } else {
+                    problem.with_fix(Fix::new(vec![TextEdit::delete(
+                        context.offset(line_idx, key_start)..context.offset(line_idx, col_idx),
+                    )]))
+                });
             }
 
-            // Find colons that are part of key-value pairs (not in strings)
-            // Simple approach: look for colons followed by space or end of line
-            let mut in_single_quote = false;
-            let mut in_double_quote = false;
-            let mut escaped = false;
-
-            for (col_idx, ch) in line.char_indices() {
-                if escaped {
-                    escaped = false;
-                    continue;
+            // Check spaces after colon
+            if let Some(' ') = next_char {
+                let spaces_after = rest.chars().take_while(|&c| c == ' ').count();
+                let value = &rest[spaces_after..];
+
+                // Spacing before a comment or trailing whitespace is left to
+                // the comments and trailing-spaces rules
+                if spaces_after > self.max_spaces_after
+                    && !value.is_empty()
+                    && !value.starts_with('#')
+                {
+                    let problem = LintProblem::new(
+                        line_idx + 1,
+                        col_idx + 2,
+                        format!(
+                            "too many spaces after colon ({} > {})",
+                            spaces_after, self.max_spaces_after
+                        ),
+                        self.name(),
+                        LintLevel::Error,
+                    )
+                    .with_range(
+                        context,
+                        line_idx + 1,
+                        col_idx + 2 + spaces_after,
+                    );
+
+                    // At least one space must remain, otherwise the colon is
+                    // no longer a mapping indicator
+                    let max_after = self.max_spaces_after.max(1);
+                    problems.push(if spaces_after > max_after {
+                        problem.with_fix(Fix::new(vec![TextEdit::delete(
+                            context.offset(line_idx, col_idx + 1 + max_after)
+                                ..context.offset(line_idx, col_idx + 1 + spaces_after),
+                        )]))
+                    } else {
+                        problem
+                    });
                 }
-
-                match ch {
-                    '\\' if in_double_quote => escaped = true,
-                    '\'' if !in_double_quote => in_single_quote = !in_single_quote,
-                    '"' if !in_single_quote => in_double_quote = !in_double_quote,
-                    ':' if !in_single_quote && !in_double_quote => {
-                        // Found a colon outside of quotes
-                        // Check if it's part of a key-value pair (followed by space, newline, or comment)
-                        let rest = &line[col_idx + 1..];
-                        let next_char = rest.chars().next();
-
-                        // This looks like a mapping colon if followed by space, nothing, or comment
-                        if matches!(next_char, None | Some(' ') | Some('#') | Some('\n')) {
-                            // Check spaces before colon
-                            let spaces_before = line[..col_idx]
-                                .chars()
-                                .rev()
-                                .take_while(|&c| c == ' ')
-                                .count();
-
-                            if spaces_before > self.max_spaces_before {
-                                problems.push(LintProblem::new(
-                                    line_idx + 1,
-                                    col_idx + 1,
-                                    format!(
-                                        "too many spaces before colon ({} > {})",
-                                        spaces_before, self.max_spaces_before
-                                    ),
-                                    self.name(),
-                                    LintLevel::Error,
-                                ));
-                            }
-
-                            // Check spaces after colon
-                            if let Some(' ') = next_char {
-                                let spaces_after = rest.chars().take_while(|&c| c == ' ').count();
-
-                                if spaces_after > self.max_spaces_after {
-                                    problems.push(LintProblem::new(
-                                        line_idx + 1,
-                                        col_idx + 2,
-                                        format!(
-                                            "too many spaces after colon ({} > {})",
-                                            spaces_after, self.max_spaces_after
-                                        ),
-                                        self.name(),
-                                        LintLevel::Error,
-                                    ));
-                                }
-                            } else if next_char.is_none() {
-                                // Colon at end of line is okay (value on next line)
-                            } else if let Some('#') = next_char {
-                                // Colon followed by comment - should have space
-                                if self.max_spaces_after > 0 {
-                                    problems.push(LintProblem::new(
-                                        line_idx + 1,
-                                        col_idx + 2,
-                                        "missing space after colon",
-                                        self.name(),
-                                        LintLevel::Error,
-                                    ));
-                                }
-                            }
-
-                            // Only check first colon in line for simplicity
-                            break;
-                        }
-                    }
-                    _ => {}
+            } else if next_char.is_none() {
+                // Colon at end of line is okay (value on next line)
+            } else if let Some('#') = next_char {
+                // Colon followed by comment - should have space
+                if self.max_spaces_after > 0 {
+                    problems.push(
+                        LintProblem::new(
+                            line_idx + 1,
+                            col_idx + 2,
+                            "missing space after colon",
+                            self.name(),
+                            LintLevel::Error,
+                        )
+                        .with_range(context, line_idx + 1, col_idx + 3),
+                    );
                 }
             }
         }
@@ -147,11 +221,16 @@ impl Rule for ColonsRule {
     fn default_level(&self) -> RuleLevel {
         RuleLevel::Error
     }
+
+    fn is_fixable(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixer::fix_with;
 
     #[test]
     fn test_correct_spacing() {
@@ -183,6 +262,9 @@ mod tests {
 
         assert_eq!(problems.len(), 1);
         assert!(problems[0].message.contains("too many spaces after colon"));
+        assert_eq!(problems[0].column, 5);
+        assert_eq!(problems[0].end_column, Some(7));
+        assert_eq!(&yaml[problems[0].span.clone().unwrap()], "  ");
     }
 
     #[test]
@@ -246,4 +328,70 @@ mod tests {
         // but yamllint might flag it. For now, we only check max spaces.
         assert!(problems.is_empty());
     }
+
+    #[test]
+    fn test_spaces_before_comment_not_flagged() {
+        let yaml = "key:   # comment\n  nested: value\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = ColonsRule::new();
+        let problems = rule.check(&context);
+
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_block_scalar_content_ignored() {
+        let yaml = "script: |\n  echo a  :  b\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = ColonsRule::new();
+        let problems = rule.check(&context);
+
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_fix_collapses_spaces() {
+        let yaml = "key  :   value\nlist:\n  - name :  x  # comment\nok: value\n";
+        let fixed = fix_with(Box::new(ColonsRule::new()), yaml).unwrap();
+        assert_eq!(
+            fixed,
+            "key: value\nlist:\n  - name: x  # comment\nok: value\n"
+        );
+        assert!(ColonsRule::new().check(&LintContext::new(fixed)).is_empty());
+    }
+
+    #[test]
+    fn test_fix_respects_configured_maximum() {
+        let yaml = "key  :    value\n";
+        let rule = ColonsRule::with_spacing(1, 2);
+
+        assert_eq!(fix_with(Box::new(rule), yaml).unwrap(), "key :  value\n");
+    }
+
+    #[test]
+    fn test_fix_respects_quotes_and_block_scalars() {
+        let yaml = "\"a  :  b\":  'c  :  d'\ntext: >\n  x  :  y\n";
+
+        assert_eq!(
+            fix_with(Box::new(ColonsRule::new()), yaml).unwrap(),
+            "\"a  :  b\": 'c  :  d'\ntext: >\n  x  :  y\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_respects_multi_line_quoted_scalars() {
+        let yaml = "key: \"first\n  b  :   c\"\nother:  'x\n  y  :  z'\n";
+
+        assert_eq!(
+            fix_with(Box::new(ColonsRule::new()), yaml).unwrap(),
+            "key: \"first\n  b  :   c\"\nother: 'x\n  y  :  z'\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_no_change() {
+        let yaml = "key: value\n";
+
+        assert!(fix_with(Box::new(ColonsRule::new()), yaml).is_none());
+    }
 }
@@ -1,7 +1,7 @@
 //! Comments rule - enforces consistent comment formatting
 
-use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::problem::{Fix, LintLevel, LintProblem, TextEdit};
+use crate::rules::{LintContext, OptionType, Rule, RuleLevel, RuleMetadata, RuleOption};
 
 /// Rule that enforces consistent comment formatting in YAML files
 ///
@@ -52,7 +52,7 @@ impl CommentsRule {
     /// Handles YAML string escaping correctly:
     /// - Single-quoted strings: '' is an escaped single quote
     /// - Double-quoted strings: backslash escapes the next character
-    fn find_comment_start(line: &str) -> Option<usize> {
+    pub(crate) fn find_comment_start(line: &str) -> Option<usize> {
         let mut in_single_quote = false;
         let mut in_double_quote = false;
         let mut chars = line.char_indices().peekable();
@@ -98,6 +98,37 @@ impl Rule for CommentsRule {
         "comments"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "comments",
+            description: "Requires a space after the `#` of comments and spacing before inline comments.",
+            rationale: "Consistently formatted comments are easier to tell apart from content.",
+            fixable: true,
+            options: &[
+                RuleOption {
+                    name: "require-starting-space",
+                    value_type: OptionType::Bool,
+                    default: Some("true"),
+                    description: "Whether a space is required after the `#`",
+                },
+                RuleOption {
+                    name: "ignore-shebangs",
+                    value_type: OptionType::Bool,
+                    default: Some("true"),
+                    description: "Whether a `#!` line at the start of a file is allowed",
+                },
+                RuleOption {
+                    name: "min-spaces-from-content",
+                    value_type: OptionType::Integer,
+                    default: Some("2"),
+                    description: "Minimum number of spaces between content and an inline comment, or -1 for no minimum",
+                },
+            ],
+            bad_example: "#comment\nkey: value # inline\n",
+            good_example: "# comment\nkey: value  # inline\n",
+        }
+    }
+
     #[allow(clippy::collapsible_if)] // Nested ifs required for MSRV 1.85 compatibility
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
@@ -130,13 +161,19 @@ impl Rule for CommentsRule {
                 // Also allow ## for section headers, #! for shebangs in non-first lines
                 if let Some(ch) = char_after_hash {
                     if ch != ' ' && ch != '#' && ch != '!' && ch != '\t' {
-                        problems.push(LintProblem::new(
-                            line_num,
-                            comment_start + 2, // Position after #
-                            "missing space after comment marker (#)",
-                            self.name(),
-                            LintLevel::Error,
-                        ));
+                        problems.push(
+                            LintProblem::new(
+                                line_num,
+                                comment_start + 2, // Position after #
+                                "missing space after comment marker (#)",
+                                self.name(),
+                                LintLevel::Error,
+                            )
+                            .with_range(context, line_num, line.trim_end().len() + 1)
+                            .with_fix(Fix::new(vec![
+                                TextEdit::insert(context.offset(line_idx, comment_start + 1), " "),
+                            ])),
+                        );
                     }
                 }
             }
@@ -151,16 +188,24 @@ impl Rule for CommentsRule {
 
                 // Only check if there's actual content before the comment
                 if !trimmed_before.is_empty() && spaces_before < self.min_spaces_from_content {
-                    problems.push(LintProblem::new(
-                        line_num,
-                        comment_start + 1,
-                        format!(
-                            "too few spaces before comment ({} < {})",
-                            spaces_before, self.min_spaces_from_content
-                        ),
-                        self.name(),
-                        LintLevel::Error,
-                    ));
+                    problems.push(
+                        LintProblem::new(
+                            line_num,
+                            comment_start + 1,
+                            format!(
+                                "too few spaces before comment ({} < {})",
+                                spaces_before, self.min_spaces_from_content
+                            ),
+                            self.name(),
+                            LintLevel::Error,
+                        )
+                        .with_range(context, line_num, line.trim_end().len() + 1)
+                        .with_fix(Fix::new(vec![TextEdit::new(
+                            context.offset(line_idx, trimmed_before.len())
+                                ..context.offset(line_idx, comment_start),
+                            " ".repeat(self.min_spaces_from_content),
+                        )])),
+                    );
                 }
             }
         }
@@ -175,82 +220,12 @@ impl Rule for CommentsRule {
     fn is_fixable(&self) -> bool {
         true
     }
-
-    #[allow(clippy::collapsible_if)] // Nested ifs required for MSRV 1.85 compatibility
-    fn fix(&self, content: &str, _problem: &LintProblem) -> Option<String> {
-        let lines: Vec<&str> = content.lines().collect();
-        let mut result_lines: Vec<String> = Vec::new();
-        let mut made_changes = false;
-
-        for (line_idx, line) in lines.iter().enumerate() {
-            let mut fixed_line = line.to_string();
-
-            // Skip empty lines
-            if line.trim().is_empty() {
-                result_lines.push(fixed_line);
-                continue;
-            }
-
-            // Find comment start position
-            if let Some(comment_start) = Self::find_comment_start(line) {
-                // Skip shebangs on first line
-                if self.ignore_shebangs && line_idx == 0 && Self::is_shebang(line) {
-                    result_lines.push(fixed_line);
-                    continue;
-                }
-
-                let after_hash = &line[comment_start..];
-
-                // Fix space after #
-                if self.require_starting_space && after_hash.len() > 1 {
-                    let char_after_hash = after_hash.chars().nth(1);
-                    if let Some(ch) = char_after_hash {
-                        if ch != ' ' && ch != '#' && ch != '!' && ch != '\t' {
-                            // Insert space after #
-                            let before = &line[..comment_start + 1];
-                            let after = &line[comment_start + 1..];
-                            fixed_line = format!("{} {}", before, after);
-                            made_changes = true;
-                        }
-                    }
-                }
-
-                // Fix inline comment spacing
-                if comment_start > 0 {
-                    let before_comment = &fixed_line[..comment_start];
-                    let trimmed_before = before_comment.trim_end();
-
-                    if !trimmed_before.is_empty() {
-                        let spaces_before = before_comment.len() - trimmed_before.len();
-                        if spaces_before < self.min_spaces_from_content {
-                            let comment_part = &fixed_line[comment_start..];
-                            let spaces = " ".repeat(self.min_spaces_from_content);
-                            fixed_line = format!("{}{}{}", trimmed_before, spaces, comment_part);
-                            made_changes = true;
-                        }
-                    }
-                }
-            }
-
-            result_lines.push(fixed_line);
-        }
-
-        if !made_changes {
-            return None;
-        }
-
-        let mut result = result_lines.join("\n");
-        if content.ends_with('\n') {
-            result.push('\n');
-        }
-
-        Some(result)
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixer::fix_with;
 
     #[test]
     fn test_proper_comment() {
@@ -441,4 +416,17 @@ mod tests {
             assert!(fixed.contains("value  #"));
         }
     }
+
+    #[test]
+    fn test_fix_attaches_edits() {
+        let yaml = "#!/bin/sh\n#top\nkey: value #inline\nother: '#not'  # ok\n";
+        let problems = CommentsRule::new().check(&LintContext::new(yaml.to_string()));
+
+        assert_eq!(problems.len(), 3);
+        assert!(problems.iter().all(|problem| problem.fix.is_some()));
+        assert_eq!(
+            fix_with(Box::new(CommentsRule::new()), yaml).unwrap(),
+            "#!/bin/sh\n# top\nkey: value  # inline\nother: '#not'  # ok\n"
+        );
+    }
 }
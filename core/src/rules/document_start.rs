@@ -1,7 +1,7 @@
 //! Document start rule - requires or forbids `---` at document start
 
-use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::problem::{Fix, LintLevel, LintProblem, TextEdit};
+use crate::rules::{LintContext, OptionType, Rule, RuleLevel, RuleMetadata, RuleOption};
 
 /// Configuration for document start requirement
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,28 +19,169 @@ pub enum DocumentStartPresence {
 pub struct DocumentStartRule {
     /// Whether `---` should be present
     pub presence: DocumentStartPresence,
+    /// Whether the fix inserts `---` after leading comments (true) or before them (false)
+    pub insert_after_comments: bool,
+}
+
+/// Location of the pieces of a single document within the file (0-based line indexes)
+#[derive(Debug, Default)]
+struct Document {
+    /// First line belonging to this document
+    start: usize,
+    /// Last `%` directive line before the document start
+    last_directive: Option<usize>,
+    /// Explicit `---` marker line
+    marker: Option<usize>,
+    /// First line with actual content
+    content: Option<usize>,
+}
+
+impl Document {
+    fn at(start: usize) -> Self {
+        Self {
+            start,
+            ..Self::default()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.marker.is_none() && self.content.is_none()
+    }
 }
 
 impl DocumentStartRule {
     /// Create a new rule (disabled by default, matching yamllint)
     pub fn new() -> Self {
-        Self {
-            presence: DocumentStartPresence::Disabled,
-        }
+        Self::with_config(DocumentStartPresence::Disabled, true)
     }
 
     /// Create a rule that requires `---`
     pub fn required() -> Self {
-        Self {
-            presence: DocumentStartPresence::Required,
-        }
+        Self::with_config(DocumentStartPresence::Required, true)
     }
 
     /// Create a rule that forbids `---`
     pub fn forbidden() -> Self {
+        Self::with_config(DocumentStartPresence::Forbidden, true)
+    }
+
+    /// Create a rule with full configuration
+    pub fn with_config(presence: DocumentStartPresence, insert_after_comments: bool) -> Self {
         Self {
-            presence: DocumentStartPresence::Forbidden,
+            presence,
+            insert_after_comments,
+        }
+    }
+
+    /// Check whether a line is the given document marker (`---` or `...`)
+    fn is_marker(line: &str, marker: &str) -> bool {
+        line.strip_prefix(marker)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+    }
+
+    /// Split the file into documents, recording where each one starts
+    fn documents<S: AsRef<str>>(lines: &[S]) -> Vec<Document> {
+        let mut documents = Vec::new();
+        let mut current = Document::at(0);
+
+        for (idx, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+
+            if Self::is_marker(line, "---") {
+                if !current.is_empty() {
+                    documents.push(std::mem::replace(&mut current, Document::at(idx)));
+                }
+                current.marker = Some(idx);
+                continue;
+            }
+
+            if Self::is_marker(line, "...") {
+                if !current.is_empty() {
+                    documents.push(current);
+                }
+                current = Document::at(idx + 1);
+                continue;
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') || current.content.is_some() {
+                continue;
+            }
+
+            // Directives are only meaningful before the document start
+            if current.is_empty() && line.starts_with('%') {
+                current.last_directive = Some(idx);
+                continue;
+            }
+
+            current.content = Some(idx);
         }
+
+        if !current.is_empty() {
+            documents.push(current);
+        }
+
+        documents
+    }
+
+    /// Fix inserting `---` at the start of a 0-based line index, which may be
+    /// one past the last line
+    fn insert_marker(context: &LintContext, line_idx: usize) -> Fix {
+        let newline = if context.content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let offset = context.offset(line_idx, 0);
+        let marker = if offset == context.content.len()
+            && !context.content.is_empty()
+            && !context.content.ends_with('\n')
+        {
+            format!("{}---{}", newline, newline)
+        } else {
+            format!("---{}", newline)
+        };
+
+        Fix::new(vec![TextEdit::insert(offset, marker)])
+    }
+
+    /// Fix inserting the missing `---` of a document
+    fn missing_marker_fix(&self, context: &LintContext, document: &Document) -> Option<Fix> {
+        let line_idx = if self.insert_after_comments {
+            document.content?
+        } else {
+            document
+                .last_directive
+                .map_or(document.start, |idx| idx + 1)
+        };
+        Some(Self::insert_marker(context, line_idx))
+    }
+
+    /// Fix removing the `---` of the first document
+    ///
+    /// Only the leading marker can go: later ones separate documents, and
+    /// directives cannot exist without an explicit document start.
+    fn forbidden_marker_fix(context: &LintContext, document: &Document) -> Option<Fix> {
+        let line_idx = document.marker?;
+        if document.last_directive.is_some() {
+            return None;
+        }
+
+        let line = context.lines.get(line_idx)?;
+        let rest = line[3..].trim_start();
+        let end = if rest.is_empty() {
+            context.offset(line_idx + 1, 0)
+        } else if rest.starts_with('#') {
+            // Keep the comment of the marker line
+            context.offset(line_idx, line.len() - rest.len())
+        } else {
+            // Content on the marker line (e.g. `--- !tag` or `--- |`) cannot be kept
+            return None;
+        };
+
+        Some(Fix::new(vec![TextEdit::delete(
+            context.offset(line_idx, 0)..end,
+        )]))
     }
 }
 
@@ -55,43 +196,105 @@ impl Rule for DocumentStartRule {
         "document-start"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "document-start",
+            description: "Requires or forbids the `---` marker at the start of documents.",
+            rationale: "An explicit marker makes where each document of a stream starts unambiguous.",
+            fixable: true,
+            options: &[
+                RuleOption {
+                    name: "present",
+                    value_type: OptionType::Bool,
+                    default: None,
+                    description: "Whether `---` is required (true) or forbidden (false)",
+                },
+                RuleOption {
+                    name: "insert-after-comments",
+                    value_type: OptionType::Bool,
+                    default: Some("true"),
+                    description: "Whether `--fix` inserts `---` after leading comments rather than before them",
+                },
+            ],
+            bad_example: "key: value\n",
+            good_example: "---\nkey: value\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         if self.presence == DocumentStartPresence::Disabled {
             return Vec::new();
         }
 
         let mut problems = Vec::new();
-
-        // Check if the document starts with `---`
-        let has_document_start = context
-            .lines
-            .first()
-            .map(|line| {
-                let trimmed = line.trim();
-                trimmed == "---" || trimmed.starts_with("--- ")
-            })
-            .unwrap_or(false);
+        let documents = Self::documents(&context.lines);
 
         match self.presence {
-            DocumentStartPresence::Required if !has_document_start => {
-                problems.push(LintProblem::new(
-                    1,
-                    1,
-                    "missing document start \"---\"",
-                    self.name(),
-                    LintLevel::Error,
-                ));
+            DocumentStartPresence::Required if documents.is_empty() => {
+                let line_idx = if self.insert_after_comments {
+                    context.lines.len()
+                } else {
+                    0
+                };
+                problems.push(
+                    LintProblem::new(
+                        1,
+                        1,
+                        "missing document start \"---\"",
+                        self.name(),
+                        LintLevel::Error,
+                    )
+                    .with_range(context, 1, 1)
+                    .with_fix(Self::insert_marker(context, line_idx)),
+                );
             }
-            DocumentStartPresence::Forbidden if has_document_start => {
-                problems.push(LintProblem::new(
-                    1,
-                    1,
-                    "found forbidden document start \"---\"",
-                    self.name(),
-                    LintLevel::Error,
-                ));
+            DocumentStartPresence::Required => {
+                for document in &documents {
+                    if let (None, Some(idx)) = (document.marker, document.content) {
+                        let problem = LintProblem::new(
+                            idx + 1,
+                            1,
+                            "missing document start \"---\"",
+                            self.name(),
+                            LintLevel::Error,
+                        )
+                        .with_range(
+                            context,
+                            idx + 1,
+                            context.lines[idx].trim_end().len() + 1,
+                        );
+                        problems.push(match self.missing_marker_fix(context, document) {
+                            Some(fix) => problem.with_fix(fix),
+                            None => problem,
+                        });
+                    }
+                }
             }
-            _ => {}
+            DocumentStartPresence::Forbidden => {
+                for (position, document) in documents.iter().enumerate() {
+                    let Some(idx) = document.marker else {
+                        continue;
+                    };
+                    let problem = LintProblem::new(
+                        idx + 1,
+                        1,
+                        "found forbidden document start \"---\"",
+                        self.name(),
+                        LintLevel::Error,
+                    )
+                    .with_range(context, idx + 1, 4);
+                    let fix = if position == 0 {
+                        Self::forbidden_marker_fix(context, document)
+                    } else {
+                        None
+                    };
+                    problems.push(match fix {
+                        Some(fix) => problem.with_fix(fix),
+                        None => problem,
+                    });
+                }
+            }
+            DocumentStartPresence::Disabled => {}
         }
 
         problems
@@ -100,6 +303,10 @@ impl Rule for DocumentStartRule {
     fn default_level(&self) -> RuleLevel {
         RuleLevel::Disable
     }
+
+    fn is_fixable(&self) -> bool {
+        self.presence != DocumentStartPresence::Disabled
+    }
 }
 
 #[cfg(test)]
@@ -190,4 +397,152 @@ mod tests {
 
         assert_eq!(problems.len(), 1);
     }
+
+    #[test]
+    fn test_required_after_leading_comments() {
+        let yaml = "# header\n---\nkey: value\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::required();
+
+        assert!(rule.check(&context).is_empty());
+    }
+
+    #[test]
+    fn test_required_multi_document() {
+        let yaml = "---\na: 1\n...\nb: 2\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::required();
+        let problems = rule.check(&context);
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 4);
+    }
+
+    #[test]
+    fn test_forbidden_multi_document() {
+        let yaml = "---\na: 1\n---\nb: 2\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::forbidden();
+        let problems = rule.check(&context);
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].line, 1);
+        assert_eq!(problems[1].line, 3);
+        assert_eq!(problems[1].end_column, Some(4));
+        assert_eq!(problems[1].span, Some(9..12));
+    }
+
+    #[test]
+    fn test_fix_required_after_comments() {
+        let yaml = "# header\n\nkey: value\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::required();
+        let problems = rule.check(&context);
+
+        assert!(rule.is_fixable());
+        assert_eq!(
+            rule.fix(yaml, &problems[0]),
+            Some("# header\n\n---\nkey: value\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fix_required_before_comments() {
+        let yaml = "# header\nkey: value\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::with_config(DocumentStartPresence::Required, false);
+        let problems = rule.check(&context);
+
+        assert_eq!(
+            rule.fix(yaml, &problems[0]),
+            Some("---\n# header\nkey: value\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fix_required_after_directives() {
+        let yaml = "# header\n%YAML 1.2\nkey: value\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::with_config(DocumentStartPresence::Required, false);
+        let problems = rule.check(&context);
+
+        assert_eq!(
+            rule.fix(yaml, &problems[0]),
+            Some("# header\n%YAML 1.2\n---\nkey: value\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fix_required_later_document() {
+        let yaml = "---\na: 1\n...\nb: 2\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::required();
+        let problems = rule.check(&context);
+
+        assert_eq!(
+            rule.fix(yaml, &problems[0]),
+            Some("---\na: 1\n...\n---\nb: 2\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fix_required_empty_file() {
+        let context = LintContext::new(String::new());
+        let rule = DocumentStartRule::required();
+        let problems = rule.check(&context);
+
+        assert_eq!(rule.fix("", &problems[0]), Some("---\n".to_string()));
+    }
+
+    #[test]
+    fn test_fix_forbidden_removes_leading_marker() {
+        let yaml = "---\nkey: value\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::forbidden();
+        let problems = rule.check(&context);
+
+        assert_eq!(
+            rule.fix(yaml, &problems[0]),
+            Some("key: value\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fix_forbidden_keeps_comment() {
+        let yaml = "--- # comment\nkey: value\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::forbidden();
+        let problems = rule.check(&context);
+
+        assert_eq!(
+            rule.fix(yaml, &problems[0]),
+            Some("# comment\nkey: value\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fix_forbidden_keeps_document_separators() {
+        let yaml = "---\na: 1\n---\nb: 2\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = DocumentStartRule::forbidden();
+        let problems = rule.check(&context);
+
+        assert_eq!(
+            rule.fix(yaml, &problems[0]),
+            Some("a: 1\n---\nb: 2\n".to_string())
+        );
+        assert_eq!(rule.fix(yaml, &problems[1]), None);
+    }
+
+    #[test]
+    fn test_fix_forbidden_with_content_or_directive_unfixable() {
+        let rule = DocumentStartRule::forbidden();
+
+        for yaml in ["--- |\n  text\n", "%YAML 1.2\n---\nkey: value\n"] {
+            let context = LintContext::new(yaml.to_string());
+            let problems = rule.check(&context);
+            assert_eq!(problems.len(), 1);
+            assert_eq!(rule.fix(yaml, &problems[0]), None);
+        }
+    }
 }
@@ -1,7 +1,7 @@
 //! Empty lines rule - limits consecutive blank lines
 
-use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::problem::{Fix, LintLevel, LintProblem, TextEdit};
+use crate::rules::{LintContext, OptionType, Rule, RuleLevel, RuleMetadata, RuleOption};
 
 /// Rule that limits consecutive empty lines in YAML files
 ///
@@ -40,6 +40,13 @@ impl EmptyLinesRule {
     fn is_empty_line(line: &str) -> bool {
         line.trim().is_empty()
     }
+
+    /// Fix deleting the lines in `first..last` (0-based)
+    fn remove_lines(context: &LintContext, first: usize, last: usize) -> Fix {
+        Fix::new(vec![TextEdit::delete(
+            context.offset(first, 0)..context.offset(last, 0),
+        )])
+    }
 }
 
 impl Default for EmptyLinesRule {
@@ -53,6 +60,37 @@ impl Rule for EmptyLinesRule {
         "empty-lines"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "empty-lines",
+            description: "Limits consecutive empty lines, and empty lines at the start and end of files.",
+            rationale: "Runs of blank lines waste space without adding structure.",
+            fixable: true,
+            options: &[
+                RuleOption {
+                    name: "max",
+                    value_type: OptionType::Integer,
+                    default: Some("2"),
+                    description: "Maximum number of consecutive empty lines",
+                },
+                RuleOption {
+                    name: "max-start",
+                    value_type: OptionType::Integer,
+                    default: Some("0"),
+                    description: "Maximum number of empty lines at the start of a file",
+                },
+                RuleOption {
+                    name: "max-end",
+                    value_type: OptionType::Integer,
+                    default: Some("0"),
+                    description: "Maximum number of empty lines at the end of a file",
+                },
+            ],
+            bad_example: "first: 1\n\n\n\nsecond: 2\n",
+            good_example: "first: 1\n\nsecond: 2\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
 
@@ -71,16 +109,28 @@ impl Rule for EmptyLinesRule {
         }
 
         if start_empty_count > self.max_start {
-            problems.push(LintProblem::new(
-                1,
-                1,
-                format!(
-                    "too many blank lines at start of file ({} > {})",
-                    start_empty_count, self.max_start
-                ),
-                self.name(),
-                LintLevel::Error,
-            ));
+            problems.push(
+                LintProblem::new(
+                    1,
+                    1,
+                    format!(
+                        "too many blank lines at start of file ({} > {})",
+                        start_empty_count, self.max_start
+                    ),
+                    self.name(),
+                    LintLevel::Error,
+                )
+                .with_range(
+                    context,
+                    start_empty_count,
+                    context.lines[start_empty_count - 1].len() + 1,
+                )
+                .with_fix(Self::remove_lines(
+                    context,
+                    self.max_start,
+                    start_empty_count,
+                )),
+            );
         }
 
         // If entire file is empty lines, don't report as end-of-file error too
@@ -100,16 +150,28 @@ impl Rule for EmptyLinesRule {
 
         if end_empty_count > self.max_end {
             let line_num = context.lines.len() - end_empty_count + 1;
-            problems.push(LintProblem::new(
-                line_num,
-                1,
-                format!(
-                    "too many blank lines at end of file ({} > {})",
-                    end_empty_count, self.max_end
-                ),
-                self.name(),
-                LintLevel::Error,
-            ));
+            problems.push(
+                LintProblem::new(
+                    line_num,
+                    1,
+                    format!(
+                        "too many blank lines at end of file ({} > {})",
+                        end_empty_count, self.max_end
+                    ),
+                    self.name(),
+                    LintLevel::Error,
+                )
+                .with_range(
+                    context,
+                    context.lines.len(),
+                    context.lines[context.lines.len() - 1].len() + 1,
+                )
+                .with_fix(Self::remove_lines(
+                    context,
+                    line_num - 1 + self.max_end,
+                    context.lines.len(),
+                )),
+            );
         }
 
         // Check consecutive empty lines in the middle
@@ -128,16 +190,24 @@ impl Rule for EmptyLinesRule {
                     let is_at_start = empty_block_start == 1;
 
                     if !is_at_start {
-                        problems.push(LintProblem::new(
-                            empty_block_start,
-                            1,
-                            format!(
-                                "too many blank lines ({} > {})",
-                                consecutive_empty, self.max
-                            ),
-                            self.name(),
-                            LintLevel::Error,
-                        ));
+                        problems.push(
+                            LintProblem::new(
+                                empty_block_start,
+                                1,
+                                format!(
+                                    "too many blank lines ({} > {})",
+                                    consecutive_empty, self.max
+                                ),
+                                self.name(),
+                                LintLevel::Error,
+                            )
+                            .with_range(context, idx, context.lines[idx - 1].len() + 1)
+                            .with_fix(Self::remove_lines(
+                                context,
+                                empty_block_start - 1 + self.max,
+                                idx,
+                            )),
+                        );
                     }
                 }
                 consecutive_empty = 0;
@@ -154,64 +224,6 @@ impl Rule for EmptyLinesRule {
     fn is_fixable(&self) -> bool {
         true
     }
-
-    fn fix(&self, content: &str, _problem: &LintProblem) -> Option<String> {
-        let lines: Vec<&str> = content.lines().collect();
-        if lines.is_empty() {
-            return None;
-        }
-
-        let mut result_lines: Vec<&str> = Vec::new();
-
-        // Skip empty lines at start (keep max_start)
-        let mut start_empty_count = 0;
-        let mut skip_until = 0;
-        for (idx, line) in lines.iter().enumerate() {
-            if line.trim().is_empty() {
-                start_empty_count += 1;
-            } else {
-                skip_until = idx;
-                break;
-            }
-        }
-        // Add allowed empty lines at start
-        let start_empty_to_add = self.max_start.min(start_empty_count);
-        result_lines.extend(std::iter::repeat_n("", start_empty_to_add));
-
-        // Process middle content
-        let mut consecutive_empty = 0;
-        for (idx, line) in lines.iter().enumerate().skip(skip_until) {
-            let is_last_content_line =
-                idx == lines.len() - 1 || lines[idx + 1..].iter().all(|l| l.trim().is_empty());
-
-            if line.trim().is_empty() {
-                consecutive_empty += 1;
-                // Don't add yet, wait until we know how many to add
-            } else {
-                // Add up to max empty lines before this content
-                let empty_to_add = consecutive_empty.min(self.max);
-                result_lines.extend(std::iter::repeat_n("", empty_to_add));
-                consecutive_empty = 0;
-
-                result_lines.push(line);
-
-                // If this is the last content line, handle trailing empty lines
-                if is_last_content_line {
-                    let end_empty_count = lines.len() - idx - 1;
-                    let end_empty_to_add = self.max_end.min(end_empty_count);
-                    result_lines.extend(std::iter::repeat_n("", end_empty_to_add));
-                    break;
-                }
-            }
-        }
-
-        let mut result = result_lines.join("\n");
-        if content.ends_with('\n') {
-            result.push('\n');
-        }
-
-        Some(result)
-    }
 }
 
 #[cfg(test)]
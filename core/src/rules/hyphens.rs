@@ -1,7 +1,8 @@
 //! Hyphens rule - controls spacing after list item hyphens
 
-use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::problem::{Fix, LintLevel, LintProblem, TextEdit};
+use crate::rules::indentation::block_scalar_content;
+use crate::rules::{LintContext, OptionType, Rule, RuleLevel, RuleMetadata, RuleOption};
 
 /// Rule that controls spacing after list item hyphens in YAML sequences
 ///
@@ -25,6 +26,44 @@ impl HyphensRule {
     pub fn with_config(max_spaces_after: usize) -> Self {
         Self { max_spaces_after }
     }
+
+    /// Fix collapsing the spaces after the hyphen at `hyphen_col` of a line
+    /// down to `spaces`. The rest of the item is shifted left to stay aligned
+    /// with its content, but never onto or before the hyphen.
+    fn collapse_fix(
+        context: &LintContext,
+        line_idx: usize,
+        hyphen_col: usize,
+        spaces_after: usize,
+        spaces: usize,
+    ) -> Fix {
+        let removed = spaces_after - spaces;
+        let mut edits = vec![TextEdit::delete(
+            context.offset(line_idx, hyphen_col + 1 + spaces)
+                ..context.offset(line_idx, hyphen_col + 1 + spaces_after),
+        )];
+
+        for (idx, line) in context.lines.iter().enumerate().skip(line_idx + 1) {
+            let trimmed = line.trim_start_matches(' ');
+            if trimmed.trim().is_empty() {
+                continue;
+            }
+
+            let indent = line.len() - trimmed.len();
+            if indent <= hyphen_col {
+                break;
+            }
+
+            let new_indent = indent.saturating_sub(removed).max(hyphen_col + 1);
+            if new_indent < indent {
+                edits.push(TextEdit::delete(
+                    context.offset(idx, new_indent)..context.offset(idx, indent),
+                ));
+            }
+        }
+
+        Fix::new(edits)
+    }
 }
 
 impl Default for HyphensRule {
@@ -38,14 +77,32 @@ impl Rule for HyphensRule {
         "hyphens"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "hyphens",
+            description: "Limits the spaces after the hyphens of sequence items.",
+            rationale: "Consistent spacing after hyphens keeps sequence items aligned.",
+            fixable: true,
+            options: &[RuleOption {
+                name: "max-spaces-after",
+                value_type: OptionType::Integer,
+                default: Some("1"),
+                description: "Maximum number of spaces after a hyphen, or -1 for no limit",
+            }],
+            bad_example: "items:\n  -   first\n  - second\n",
+            good_example: "items:\n  - first\n  - second\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
+        let in_block_scalar = block_scalar_content(&context.lines);
 
         for (line_idx, line) in context.lines.iter().enumerate() {
             let trimmed = line.trim_start();
 
-            // Skip empty lines and comments
-            if trimmed.is_empty() || trimmed.starts_with('#') {
+            // Skip empty lines, comments and block scalar contents
+            if trimmed.is_empty() || trimmed.starts_with('#') || in_block_scalar[line_idx] {
                 continue;
             }
 
@@ -83,20 +140,28 @@ impl Rule for HyphensRule {
                 // or an anchor/alias, so we should be careful
                 let first_char = after_hyphen.chars().next();
                 if !matches!(first_char, Some('|') | Some('>') | Some('&') | Some('*')) {
-                    problems.push(LintProblem::new(
-                        line_idx + 1,
-                        leading_spaces + 2, // Position after the hyphen
-                        "too few spaces after hyphen",
-                        self.name(),
-                        LintLevel::Error,
-                    ));
+                    let width = first_char.map_or(1, char::len_utf8);
+                    problems.push(
+                        LintProblem::new(
+                            line_idx + 1,
+                            leading_spaces + 2, // Position after the hyphen
+                            "too few spaces after hyphen",
+                            self.name(),
+                            LintLevel::Error,
+                        )
+                        .with_range(
+                            context,
+                            line_idx + 1,
+                            leading_spaces + 2 + width,
+                        ),
+                    );
                 }
                 continue;
             }
 
             // Check if too many spaces after hyphen
             if spaces_after > self.max_spaces_after {
-                problems.push(LintProblem::new(
+                let problem = LintProblem::new(
                     line_idx + 1,
                     leading_spaces + 2, // Position after the hyphen
                     format!(
@@ -105,7 +170,27 @@ impl Rule for HyphensRule {
                     ),
                     self.name(),
                     LintLevel::Error,
-                ));
+                )
+                .with_range(
+                    context,
+                    line_idx + 1,
+                    leading_spaces + 2 + spaces_after,
+                );
+
+                // At least one space must remain for the hyphen to start a list item
+                let spaces = self.max_spaces_after.max(1);
+                let value = after_hyphen.trim_start();
+                problems.push(if spaces_after > spaces && !value.starts_with('#') {
+                    problem.with_fix(Self::collapse_fix(
+                        context,
+                        line_idx,
+                        leading_spaces,
+                        spaces_after,
+                        spaces,
+                    ))
+                } else {
+                    problem
+                });
             }
         }
 
@@ -115,11 +200,16 @@ impl Rule for HyphensRule {
     fn default_level(&self) -> RuleLevel {
         RuleLevel::Error
     }
+
+    fn is_fixable(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixer::fix_with;
 
     #[test]
     fn test_correct_spacing() {
@@ -141,6 +231,8 @@ mod tests {
         assert_eq!(problems.len(), 1);
         assert_eq!(problems[0].line, 2);
         assert!(problems[0].message.contains("too many spaces after hyphen"));
+        assert_eq!(problems[0].end_column, Some(6));
+        assert_eq!(&yaml[problems[0].span.clone().unwrap()], "  ");
     }
 
     #[test]
@@ -303,4 +395,58 @@ mod tests {
 
         assert!(problems.is_empty());
     }
+
+    #[test]
+    fn test_block_scalar_content_ignored() {
+        let yaml = "text: |\n  -   not a list item\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = HyphensRule::new();
+        let problems = rule.check(&context);
+
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_fix_collapses_spaces() {
+        let yaml = "list:\n  -   item1\n  -  \"quoted  value\"\n  - item3\n";
+        let fixed = fix_with(Box::new(HyphensRule::new()), yaml).unwrap();
+        assert_eq!(
+            fixed,
+            "list:\n  - item1\n  - \"quoted  value\"\n  - item3\n"
+        );
+        assert!(
+            HyphensRule::new()
+                .check(&LintContext::new(fixed))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_fix_shifts_item_content() {
+        let yaml =
+            "list:\n  -   name: a\n      nested:\n        -  x\n  -   name: b\nnext: value\n";
+
+        assert_eq!(
+            fix_with(Box::new(HyphensRule::new()), yaml).unwrap(),
+            "list:\n  - name: a\n    nested:\n      - x\n  - name: b\nnext: value\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_custom_max_spaces() {
+        let yaml = "list:\n  -    item\n";
+        let rule = HyphensRule::with_config(2);
+
+        assert_eq!(
+            fix_with(Box::new(rule), yaml).unwrap(),
+            "list:\n  -  item\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_no_change() {
+        let yaml = "list:\n  - item\n";
+
+        assert!(fix_with(Box::new(HyphensRule::new()), yaml).is_none());
+    }
 }
@@ -1,7 +1,7 @@
 //! Indentation rule - validates consistent indentation
 
 use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::rules::{LintContext, OptionType, Rule, RuleLevel, RuleMetadata, RuleOption};
 
 /// Indentation configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,33 +12,424 @@ pub enum IndentSpaces {
     Consistent,
 }
 
+/// Whether block sequences must be indented under their parent key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentSequences {
+    /// Sequences must be indented (`key:\n  - item`)
+    Indented,
+    /// Sequences must not be indented (`key:\n- item`)
+    NotIndented,
+    /// Either style is accepted
+    Whatever,
+    /// The first sequence in the file decides the style for the rest
+    Consistent,
+}
+
 /// Rule that checks indentation consistency
 #[derive(Debug)]
 pub struct IndentationRule {
     /// Number of spaces for indentation
     pub spaces: IndentSpaces,
+    /// Indentation style for block sequences nested in mappings
+    pub indent_sequences: IndentSequences,
+    /// Also check the indentation of multi-line scalars
+    pub check_multi_line_strings: bool,
+}
+
+/// Block scalar (`|` or `>`) whose content lines are being skipped
+#[derive(Debug, Clone, Copy)]
+struct BlockScalar {
+    /// Content ends at the first non-empty line indented at most this much
+    parent_indent: usize,
+    /// Indentation expected for content lines
+    content_indent: usize,
+}
+
+/// Line continuing a node that started on an earlier line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Continuation {
+    /// Inside a flow collection (`[...]` or `{...}`)
+    Flow,
+    /// Inside a multi-line scalar; `expected` is where yamllint wants its
+    /// lines to start
+    Scalar { expected: usize },
+}
+
+/// Indentation level tracked while re-indenting a document
+#[derive(Debug, Clone, Copy)]
+struct Level {
+    /// Column in the original content
+    orig: usize,
+    /// Column in the fixed content
+    new: usize,
+    /// Sequence written at the same column as its parent key
+    sequence_under_key: bool,
+}
+
+/// Node whose block content starts on the following line
+#[derive(Debug, Clone, Copy)]
+struct OpenNode {
+    orig: usize,
+    new: usize,
+    /// `key:` (as opposed to a bare `-`)
+    is_key: bool,
+}
+
+/// Tracks flow collections and quoted scalars that span several lines
+#[derive(Debug, Default)]
+struct FlowState {
+    depth: usize,
+    quote: Option<char>,
+}
+
+impl FlowState {
+    /// Whether a flow collection or quoted scalar is still open
+    fn is_open(&self) -> bool {
+        self.depth > 0 || self.quote.is_some()
+    }
+
+    /// Update the state with the given text (a node value or a continuation line)
+    fn scan(&mut self, text: &str) {
+        let mut chars = text.char_indices().peekable();
+        let mut prev = None;
+
+        while let Some((idx, ch)) = chars.next() {
+            match self.quote {
+                Some('\'') => {
+                    if ch == '\'' {
+                        // '' is an escaped single quote
+                        if chars.peek().is_some_and(|&(_, next)| next == '\'') {
+                            chars.next();
+                        } else {
+                            self.quote = None;
+                        }
+                    }
+                }
+                Some(_) => match ch {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => self.quote = None,
+                    _ => {}
+                },
+                None => match ch {
+                    // Quotes only start a scalar at the beginning of a node
+                    '\'' | '"'
+                        if matches!(prev, None | Some(' ' | '[' | '{' | ',' | ':' | '-' | '?')) =>
+                    {
+                        self.quote = Some(ch);
+                    }
+                    '[' | '{' => self.depth += 1,
+                    ']' | '}' => self.depth = self.depth.saturating_sub(1),
+                    '#' if idx == 0 || prev == Some(' ') => break,
+                    _ => {}
+                },
+            }
+            prev = Some(ch);
+        }
+    }
 }
 
 impl IndentationRule {
     /// Create a new indentation rule with consistent mode
     pub fn new() -> Self {
-        Self {
-            spaces: IndentSpaces::Consistent,
-        }
+        Self::with_config(IndentSpaces::Consistent, IndentSequences::Indented, false)
     }
 
     /// Create an indentation rule with fixed spaces
     pub fn with_spaces(spaces: usize) -> Self {
-        Self {
-            spaces: IndentSpaces::Fixed(spaces),
-        }
+        Self::with_config(
+            IndentSpaces::Fixed(spaces),
+            IndentSequences::Indented,
+            false,
+        )
     }
 
     /// Create an indentation rule with consistent mode
     pub fn consistent() -> Self {
+        Self::new()
+    }
+
+    /// Create an indentation rule with custom settings
+    pub fn with_config(
+        spaces: IndentSpaces,
+        indent_sequences: IndentSequences,
+        check_multi_line_strings: bool,
+    ) -> Self {
         Self {
-            spaces: IndentSpaces::Consistent,
+            spaces,
+            indent_sequences,
+            check_multi_line_strings,
+        }
+    }
+
+    /// Check the first item of a block sequence against its parent key
+    fn check_sequence_indent(
+        &self,
+        parent_indent: usize,
+        current_indent: usize,
+        indent_size: usize,
+        first_style: &mut Option<bool>,
+    ) -> Option<usize> {
+        let indented = current_indent > parent_indent;
+        let expect_indented = match self.indent_sequences {
+            IndentSequences::Indented => true,
+            IndentSequences::NotIndented => false,
+            IndentSequences::Whatever => indented,
+            IndentSequences::Consistent => *first_style.get_or_insert(indented),
+        };
+
+        let expected = if expect_indented {
+            parent_indent + indent_size
+        } else {
+            parent_indent
+        };
+
+        (current_indent != expected).then_some(expected)
+    }
+
+    /// Re-indent block mappings and sequences to the configured width.
+    ///
+    /// Block scalar contents, flow collections and multi-line quoted or plain
+    /// scalars are shifted together with the line that starts them, so their
+    /// values are preserved. Returns `None` if nothing changes.
+    fn reindent(&self, content: &str) -> Option<String> {
+        let context = LintContext::new(content.to_string());
+        let indent_size = match self.spaces {
+            IndentSpaces::Fixed(n) => n,
+            IndentSpaces::Consistent => detect_indent_size(&context),
+        };
+
+        if indent_size == 0 {
+            return None;
+        }
+
+        let lines = &context.lines;
+        let mut new_indents: Vec<Option<usize>> = vec![None; lines.len()];
+        let mut comment_lines = Vec::new();
+
+        let mut levels = vec![Level {
+            orig: 0,
+            new: 0,
+            sequence_under_key: false,
+        }];
+        let mut open_node: Option<OpenNode> = None;
+        let mut sequence_style: Option<bool> = None;
+        // (parent indent, shift) of the block scalar being copied
+        let mut block_scalar: Option<(usize, isize)> = None;
+        // Shift applied to lines of an unterminated flow collection or quoted scalar
+        let mut continuation: Option<isize> = None;
+        let mut flow = FlowState::default();
+        let mut last_shift: isize = 0;
+
+        for (line_idx, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            let orig = line.len() - line.trim_start_matches(' ').len();
+
+            if let Some((parent_indent, shift)) = block_scalar {
+                if trimmed.is_empty() {
+                    continue;
+                }
+                if orig > parent_indent {
+                    new_indents[line_idx] = Some(orig.saturating_add_signed(shift));
+                    continue;
+                }
+                block_scalar = None;
+            }
+
+            if let Some(shift) = continuation {
+                if !trimmed.is_empty() && !line.starts_with('\t') {
+                    new_indents[line_idx] = Some(orig.saturating_add_signed(shift));
+                }
+                flow.scan(trimmed);
+                if !flow.is_open() {
+                    continuation = None;
+                }
+                continue;
+            }
+
+            // Tabs are reported but cannot be safely re-indented
+            if trimmed.is_empty() || line[orig..].starts_with('\t') {
+                continue;
+            }
+
+            if trimmed.starts_with('#') {
+                comment_lines.push(line_idx);
+                continue;
+            }
+
+            if trimmed.starts_with("---") || trimmed.starts_with("...") {
+                levels.truncate(1);
+                open_node = None;
+                continue;
+            }
+
+            let is_item = trimmed == "-" || trimmed.starts_with("- ");
+
+            while levels.len() > 1 {
+                let top = levels[levels.len() - 1];
+                if top.orig > orig || (top.orig == orig && top.sequence_under_key && !is_item) {
+                    levels.pop();
+                } else {
+                    break;
+                }
+            }
+
+            let opened = open_node.take().filter(|node| orig >= node.orig);
+            let top = levels[levels.len() - 1];
+
+            let new = match opened {
+                // First item of a sequence nested under a key
+                Some(node) if node.is_key && is_item => {
+                    let indented = orig > node.orig;
+                    let want_indented = match self.indent_sequences {
+                        IndentSequences::Indented => true,
+                        IndentSequences::NotIndented => false,
+                        IndentSequences::Whatever => indented,
+                        IndentSequences::Consistent => *sequence_style.get_or_insert(indented),
+                    };
+                    let new = if want_indented {
+                        node.new + indent_size
+                    } else {
+                        node.new
+                    };
+                    levels.push(Level {
+                        orig,
+                        new,
+                        sequence_under_key: !indented,
+                    });
+                    new
+                }
+                // Block content of a key or bare `-`
+                Some(node) if orig > node.orig => {
+                    let new = node.new + indent_size;
+                    levels.push(Level {
+                        orig,
+                        new,
+                        sequence_under_key: false,
+                    });
+                    new
+                }
+                _ if orig == top.orig => top.new,
+                // Continuation of a multi-line plain scalar
+                _ => {
+                    new_indents[line_idx] = Some(orig.saturating_add_signed(last_shift));
+                    continue;
+                }
+            };
+
+            new_indents[line_idx] = Some(new);
+            let shift = new as isize - orig as isize;
+            last_shift = shift;
+
+            // Walk through `- ` indicators; each one starts a new level
+            let mut body = trimmed;
+            let mut body_orig = orig;
+            let mut body_new = new;
+            let mut item_indent = None;
+            while let Some(rest) = body
+                .strip_prefix('-')
+                .filter(|r| r.is_empty() || r.starts_with(' '))
+            {
+                item_indent = Some(body_orig);
+                let inner = rest.trim_start();
+                if inner.is_empty() || inner.starts_with('#') {
+                    open_node = Some(OpenNode {
+                        orig: body_orig,
+                        new: body_new,
+                        is_key: false,
+                    });
+                    break;
+                }
+                let offset = body.len() - inner.len();
+                body_orig += offset;
+                body_new += offset;
+                levels.push(Level {
+                    orig: body_orig,
+                    new: body_new,
+                    sequence_under_key: false,
+                });
+                body = inner;
+            }
+
+            if open_node.is_some() {
+                continue;
+            }
+
+            if is_block_scalar_header(body) {
+                block_scalar = Some((item_indent.unwrap_or(body_orig), shift));
+            } else if let Some(value) = mapping_value(body) {
+                if is_properties_only(value) {
+                    open_node = Some(OpenNode {
+                        orig: body_orig,
+                        new: body_new,
+                        is_key: true,
+                    });
+                } else if is_block_scalar_header(value) {
+                    block_scalar = Some((body_orig, shift));
+                } else {
+                    flow.scan(value);
+                }
+            } else {
+                flow.scan(body);
+            }
+
+            if flow.is_open() {
+                continuation = Some(shift);
+            }
+        }
+
+        // Comments follow the line they are aligned with
+        for line_idx in comment_lines {
+            let orig = lines[line_idx].len() - lines[line_idx].trim_start().len();
+            let aligned = |idx: &usize| {
+                let other = &lines[*idx];
+                (other.len() - other.trim_start().len() == orig)
+                    .then_some(new_indents[*idx])
+                    .flatten()
+            };
+            new_indents[line_idx] = (line_idx + 1..lines.len())
+                .find(|idx| new_indents[*idx].is_some())
+                .and_then(|idx| aligned(&idx))
+                .or_else(|| {
+                    (0..line_idx)
+                        .rev()
+                        .find(|idx| new_indents[*idx].is_some())
+                        .and_then(|idx| aligned(&idx))
+                });
+        }
+
+        // Each line keeps its own ending, so CRLF files stay CRLF
+        let endings = content.split_inclusive('\n').map(|line| {
+            if line.ends_with("\r\n") {
+                "\r\n"
+            } else if line.ends_with('\n') {
+                "\n"
+            } else {
+                ""
+            }
+        });
+
+        let mut changed = false;
+        let mut result = String::with_capacity(content.len());
+        for ((line, new_indent), ending) in lines.iter().zip(&new_indents).zip(endings) {
+            let body = line.trim_start_matches(' ');
+            match new_indent {
+                Some(new) if *new != line.len() - body.len() => {
+                    changed = true;
+                    result.push_str(&" ".repeat(*new));
+                    result.push_str(body);
+                }
+                _ => result.push_str(line),
+            }
+            result.push_str(ending);
+        }
+
+        if !changed {
+            return None;
         }
+
+        Some(result)
     }
 }
 
@@ -53,6 +444,37 @@ impl Rule for IndentationRule {
         "indentation"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "indentation",
+            description: "Requires consistent indentation of block mappings and sequences.",
+            rationale: "Inconsistent indentation is hard to read and can silently change the structure of a document.",
+            fixable: true,
+            options: &[
+                RuleOption {
+                    name: "spaces",
+                    value_type: OptionType::IntegerOr(&["consistent"]),
+                    default: Some("consistent"),
+                    description: "Width of an indentation level, or `consistent` to use the first one found",
+                },
+                RuleOption {
+                    name: "indent-sequences",
+                    value_type: OptionType::BoolOr(&["whatever", "consistent"]),
+                    default: Some("true"),
+                    description: "Whether block sequences are indented under their parent key",
+                },
+                RuleOption {
+                    name: "check-multi-line-strings",
+                    value_type: OptionType::Bool,
+                    default: Some("false"),
+                    description: "Whether the lines of multi-line scalars are checked too",
+                },
+            ],
+            bad_example: "root:\n  child:\n     leaf: 1\n",
+            good_example: "root:\n  child:\n    leaf: 1\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
 
@@ -69,23 +491,102 @@ impl Rule for IndentationRule {
 
         let mut expected_indent: Option<usize> = None;
         let mut indent_stack: Vec<usize> = vec![0];
+        let mut block_scalar: Option<BlockScalar> = None;
+        // Column of a key whose value starts on the next line
+        let mut pending_parent: Option<usize> = None;
+        // Whether the first nested sequence was indented (for `consistent`)
+        let mut sequence_style: Option<bool> = None;
+        // Content columns of the enclosing list items
+        let mut item_bases: Vec<usize> = Vec::new();
+        let continuations = continuation_lines(&context.lines);
 
         for (line_idx, line) in context.lines.iter().enumerate() {
-            // Skip empty lines and comment-only lines
             let trimmed = line.trim();
+
+            // Block scalar contents are free-form text, not YAML structure
+            if let Some(scalar) = block_scalar {
+                if trimmed.is_empty() {
+                    continue;
+                }
+
+                let indent = line.len() - line.trim_start_matches(' ').len();
+                if indent > scalar.parent_indent {
+                    if self.check_multi_line_strings && indent != scalar.content_indent {
+                        problems.push(
+                            LintProblem::new(
+                                line_idx + 1,
+                                indent + 1,
+                                format!(
+                                    "wrong indentation: expected {} but got {}",
+                                    scalar.content_indent, indent
+                                ),
+                                self.name(),
+                                LintLevel::Error,
+                            )
+                            .with_range(
+                                context,
+                                line_idx + 1,
+                                line.trim_end().len() + 1,
+                            ),
+                        );
+                    }
+                    continue;
+                }
+
+                block_scalar = None;
+            }
+
+            // Lines of a multi-line scalar start where the scalar does (one
+            // column further for quoted scalars); flow collections are left
+            // alone
+            match continuations[line_idx] {
+                Some(Continuation::Scalar { expected }) => {
+                    let indent = line.len() - line.trim_start_matches(' ').len();
+                    if self.check_multi_line_strings && indent != expected {
+                        problems.push(
+                            LintProblem::new(
+                                line_idx + 1,
+                                indent + 1,
+                                format!(
+                                    "wrong indentation: expected {} but got {}",
+                                    expected, indent
+                                ),
+                                self.name(),
+                                LintLevel::Error,
+                            )
+                            .with_range(
+                                context,
+                                line_idx + 1,
+                                line.trim_end().len() + 1,
+                            ),
+                        );
+                    }
+                    continue;
+                }
+                Some(Continuation::Flow) => continue,
+                None => {}
+            }
+
+            // Skip empty lines and comment-only lines
             if trimmed.is_empty() || trimmed.starts_with('#') {
                 continue;
             }
 
+            let parent_key = pending_parent.take();
+
             // Check for tabs
             if line.starts_with('\t') || line.contains("\t ") || line.contains(" \t") {
-                problems.push(LintProblem::new(
-                    line_idx + 1,
-                    1,
-                    "found tab character in indentation",
-                    self.name(),
-                    LintLevel::Error,
-                ));
+                let indentation = line.len() - line.trim_start().len();
+                problems.push(
+                    LintProblem::new(
+                        line_idx + 1,
+                        1,
+                        "found tab character in indentation",
+                        self.name(),
+                        LintLevel::Error,
+                    )
+                    .with_range(context, line_idx + 1, indentation + 1),
+                );
                 continue;
             }
 
@@ -96,21 +597,31 @@ impl Rule for IndentationRule {
             if trimmed.starts_with("---") || trimmed.starts_with("...") {
                 expected_indent = Some(0);
                 indent_stack = vec![0];
+                item_bases.clear();
                 continue;
             }
 
+            // Content of a list item is aligned on the column after `- `
+            while item_bases.last().is_some_and(|&base| base > current_indent) {
+                item_bases.pop();
+            }
+            let base = item_bases.last().copied().unwrap_or(0);
+
             // Check if indentation is a multiple of indent_size
-            if current_indent % indent_size != 0 {
-                problems.push(LintProblem::new(
-                    line_idx + 1,
-                    1,
-                    format!(
-                        "wrong indentation: expected multiple of {} but got {}",
-                        indent_size, current_indent
-                    ),
-                    self.name(),
-                    LintLevel::Error,
-                ));
+            if (current_indent - base) % indent_size != 0 {
+                problems.push(
+                    LintProblem::new(
+                        line_idx + 1,
+                        1,
+                        format!(
+                            "wrong indentation: expected multiple of {} but got {}",
+                            indent_size, current_indent
+                        ),
+                        self.name(),
+                        LintLevel::Error,
+                    )
+                    .with_range(context, line_idx + 1, current_indent + 1),
+                );
                 continue;
             }
 
@@ -119,6 +630,57 @@ impl Rule for IndentationRule {
                 // List item - adjust expectations
                 let list_indent = current_indent;
 
+                // First item of a sequence nested directly under a key
+                let sequence_problem = parent_key.and_then(|parent| {
+                    self.check_sequence_indent(
+                        parent,
+                        list_indent,
+                        indent_size,
+                        &mut sequence_style,
+                    )
+                });
+                if let Some(expected) = sequence_problem {
+                    problems.push(
+                        LintProblem::new(
+                            line_idx + 1,
+                            list_indent + 1,
+                            format!(
+                                "wrong indentation: expected {} but got {}",
+                                expected, list_indent
+                            ),
+                            self.name(),
+                            LintLevel::Error,
+                        )
+                        .with_range(
+                            context,
+                            line_idx + 1,
+                            line.trim_end().len() + 1,
+                        ),
+                    );
+                }
+
+                // Look at what follows the hyphen: a block scalar or a key
+                let item = trimmed[1..].trim_start();
+                let item_column = list_indent + (trimmed.len() - item.len());
+                if !item.is_empty() {
+                    item_bases.push(item_column);
+                }
+                if is_block_scalar_header(item) {
+                    block_scalar = Some(BlockScalar {
+                        parent_indent: list_indent,
+                        content_indent: item_column + indent_size,
+                    });
+                } else if let Some(value) = mapping_value(item) {
+                    if is_properties_only(value) {
+                        pending_parent = Some(item_column);
+                    } else if is_block_scalar_header(value) {
+                        block_scalar = Some(BlockScalar {
+                            parent_indent: item_column,
+                            content_indent: item_column + indent_size,
+                        });
+                    }
+                }
+
                 // List items should be at a valid indentation level
                 #[allow(clippy::collapsible_if)]
                 if let Some(parent_indent) = indent_stack.last() {
@@ -132,25 +694,34 @@ impl Rule for IndentationRule {
                     }
                 }
 
-                // Update expected indent for next line
-                expected_indent = Some(list_indent + indent_size);
+                // Update expected indent for next line; the nested content of
+                // `- key:` is indented relative to the key
+                expected_indent = Some(pending_parent.unwrap_or(list_indent) + indent_size);
                 indent_stack.push(list_indent);
                 continue;
             }
 
             // Check if indentation matches expectation
             if let Some(expected) = expected_indent {
-                if current_indent > expected && (current_indent - expected) % indent_size != 0 {
-                    problems.push(LintProblem::new(
-                        line_idx + 1,
-                        1,
-                        format!(
-                            "wrong indentation: expected {} but got {}",
-                            expected, current_indent
+                let aligned = expected.max(base);
+                if current_indent > aligned && (current_indent - aligned) % indent_size != 0 {
+                    problems.push(
+                        LintProblem::new(
+                            line_idx + 1,
+                            1,
+                            format!(
+                                "wrong indentation: expected {} but got {}",
+                                expected, current_indent
+                            ),
+                            self.name(),
+                            LintLevel::Error,
+                        )
+                        .with_range(
+                            context,
+                            line_idx + 1,
+                            current_indent + 1,
                         ),
-                        self.name(),
-                        LintLevel::Error,
-                    ));
+                    );
                 }
 
                 // Update stack based on current indentation
@@ -178,12 +749,18 @@ impl Rule for IndentationRule {
             }
 
             // Check for key-value pairs to update expectations
-            if line.contains(':') && !trimmed.starts_with('#') {
-                // This might be a key with nested values
-                let after_colon = line.split(':').nth(1).unwrap_or("").trim();
-                if after_colon.is_empty() || after_colon.starts_with('#') {
+            if let Some(value) = mapping_value(trimmed) {
+                if value.is_empty() {
                     // Key with no value on same line - expect indented content
                     expected_indent = Some(current_indent + indent_size);
+                    pending_parent = Some(current_indent);
+                } else if is_properties_only(value) {
+                    pending_parent = Some(current_indent);
+                } else if is_block_scalar_header(value) {
+                    block_scalar = Some(BlockScalar {
+                        parent_indent: current_indent,
+                        content_indent: current_indent + indent_size,
+                    });
                 }
             }
         }
@@ -194,18 +771,268 @@ impl Rule for IndentationRule {
     fn default_level(&self) -> RuleLevel {
         RuleLevel::Error
     }
+
+    fn is_fixable(&self) -> bool {
+        true
+    }
+
+    fn fix(&self, content: &str, _problem: &LintProblem) -> Option<String> {
+        self.reindent(content)
+    }
 }
 
-/// Detect the indentation size used in the document
+/// Return the value following the key of a `key: value` line (without any
+/// trailing comment), or `None` if the text is not a mapping entry
+fn mapping_value(text: &str) -> Option<&str> {
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut colon = None;
+
+    for (idx, ch) in text.char_indices() {
+        match ch {
+            '\'' if !in_double_quote => in_single_quote = !in_single_quote,
+            '"' if !in_single_quote => in_double_quote = !in_double_quote,
+            // A comment can only start after whitespace
+            '#' if !in_single_quote
+                && !in_double_quote
+                && (idx == 0 || text[..idx].ends_with([' ', '\t'])) =>
+            {
+                break;
+            }
+            ':' if !in_single_quote && !in_double_quote => {
+                let rest = &text[idx + 1..];
+                if rest.is_empty() || rest.starts_with([' ', '\t']) {
+                    colon = Some(idx);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let rest = text[colon? + 1..].trim();
+    if rest.starts_with('#') {
+        Some("")
+    } else {
+        Some(rest)
+    }
+}
+
+/// Check whether a mapping value is empty apart from a tag or anchor
+/// (`key:`, `key: &anchor`, `key: !!map`), so its content follows on the next lines
+fn is_properties_only(value: &str) -> bool {
+    value
+        .split_whitespace()
+        .take_while(|token| !token.starts_with('#'))
+        .all(|token| token.starts_with('!') || token.starts_with('&'))
+}
+
+/// Check whether a node value starts a block scalar (`|`, `>-`, `|2+`, ...),
+/// optionally preceded by a tag or anchor and followed by a comment
+fn is_block_scalar_header(value: &str) -> bool {
+    let mut tokens = value
+        .split_whitespace()
+        .skip_while(|token| token.starts_with('!') || token.starts_with('&'));
+
+    let Some(header) = tokens.next() else {
+        return false;
+    };
+
+    let mut chars = header.chars();
+    if !matches!(chars.next(), Some('|') | Some('>')) {
+        return false;
+    }
+
+    if !chars.all(|c| c == '+' || c == '-' || c.is_ascii_digit()) {
+        return false;
+    }
+
+    tokens.next().is_none_or(|token| token.starts_with('#'))
+}
+
+/// Mark the lines that belong to the contents of a block scalar (`|` or `>`),
+/// which are free-form text rather than YAML structure
+pub(crate) fn block_scalar_content(lines: &[String]) -> Vec<bool> {
+    let mut content = vec![false; lines.len()];
+    // Content ends at the first non-empty line indented at most this much
+    let mut parent_indent: Option<usize> = None;
+
+    for (line_idx, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some(parent) = parent_indent {
+            if trimmed.is_empty() || indent > parent {
+                content[line_idx] = true;
+                continue;
+            }
+            parent_indent = None;
+        }
+
+        if trimmed.starts_with('#') {
+            continue;
+        }
+
+        // `- |` is bounded by the hyphen, `- key: |` by the key
+        let (node, node_indent) = match trimmed.strip_prefix("- ") {
+            Some(item) if is_block_scalar_header(item) => (item, indent),
+            Some(item) => {
+                let item = item.trim_start();
+                (item, indent + trimmed.len() - item.len())
+            }
+            None => (trimmed, indent),
+        };
+
+        if is_block_scalar_header(node) || mapping_value(node).is_some_and(is_block_scalar_header) {
+            parent_indent = Some(node_indent);
+        }
+    }
+
+    content
+}
+
+/// Mark the lines that continue a flow collection or a quoted or plain scalar
+/// started on an earlier line; like block scalar contents, they are not block
+/// structure
+fn continuation_lines(lines: &[String]) -> Vec<Option<Continuation>> {
+    let in_block_scalar = block_scalar_content(lines);
+    let mut continuations = vec![None; lines.len()];
+    let mut flow = FlowState::default();
+    let mut open: Option<Continuation> = None;
+    // (column the lines must be indented past, start column) of a plain scalar
+    let mut plain: Option<(usize, usize)> = None;
+    // Column of a key whose value starts on the next line
+    let mut pending_key: Option<usize> = None;
+
+    for (line_idx, line) in lines.iter().enumerate() {
+        if in_block_scalar[line_idx] {
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if let Some(continuation) = open {
+            if !trimmed.is_empty() {
+                continuations[line_idx] = Some(continuation);
+            }
+            flow.scan(trimmed);
+            if !flow.is_open() {
+                open = None;
+            }
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start_matches(' ').len();
+
+        // A plain scalar goes on until a comment or a line that is not
+        // indented past its parent; tab indentation is reported instead
+        if let Some((parent, start)) = plain {
+            if indent > parent && !trimmed.starts_with('#') && !line[indent..].starts_with('\t') {
+                continuations[line_idx] = Some(Continuation::Scalar { expected: start });
+                continue;
+            }
+            plain = None;
+        }
+
+        if trimmed.starts_with('#') {
+            continue;
+        }
+
+        let mut parent = pending_key.take();
+        if trimmed.starts_with("---") || trimmed.starts_with("...") {
+            continue;
+        }
+
+        // Walk through `- ` indicators to the node itself
+        let mut body = trimmed;
+        let mut column = indent;
+        while let Some(rest) = body
+            .strip_prefix('-')
+            .filter(|r| r.is_empty() || r.starts_with(' '))
+        {
+            parent = Some(column);
+            let inner = rest.trim_start();
+            column += body.len() - inner.len();
+            body = inner;
+        }
+
+        let value = match mapping_value(body) {
+            Some(value) if value.is_empty() || is_properties_only(value) => {
+                pending_key = Some(column);
+                continue;
+            }
+            // The value is a subslice of the body
+            Some(value) => {
+                parent = Some(column);
+                column += value.as_ptr() as usize - body.as_ptr() as usize;
+                value
+            }
+            None => body,
+        };
+
+        match value.chars().next() {
+            // Only a node starting with a quote or bracket can span lines
+            // this way
+            Some('"' | '\'' | '[' | '{') => {
+                flow.scan(value);
+                if flow.is_open() {
+                    open = Some(if flow.depth > 0 {
+                        Continuation::Flow
+                    } else {
+                        Continuation::Scalar {
+                            expected: column + 1,
+                        }
+                    });
+                }
+            }
+            // Block scalars, aliases, properties and reserved indicators do
+            // not start a plain scalar
+            Some('|' | '>' | '*' | '&' | '!' | '%' | '@' | '`' | '?' | ']' | '}') | None => {}
+            // A plain scalar at the top level has no parent to bound it
+            Some(_) => plain = parent.map(|parent| (parent, column)),
+        }
+    }
+
+    continuations
+}
+
+/// Mark the lines that continue a quoted or plain scalar started on an
+/// earlier line, whose text belongs to the scalar rather than to the mapping
+pub(crate) fn scalar_continuation_lines(lines: &[String]) -> Vec<bool> {
+    continuation_lines(lines)
+        .into_iter()
+        .map(|continuation| matches!(continuation, Some(Continuation::Scalar { .. })))
+        .collect()
+}
+
+/// Detect the indentation size used in the document, 2 if nothing is
+/// indented
 fn detect_indent_size(context: &LintContext) -> usize {
+    detected_indent_size(context).unwrap_or(2)
+}
+
+/// Detect the indentation size used in the document, `None` if nothing is
+/// indented
+pub fn detected_indent_size(context: &LintContext) -> Option<usize> {
     let mut indents = Vec::new();
 
     let mut prev_indent = 0;
+    let in_block_scalar = block_scalar_content(&context.lines);
+    let continuations = continuation_lines(&context.lines);
 
-    for line in &context.lines {
-        // Skip empty lines and comments
+    for (line_idx, line) in context.lines.iter().enumerate() {
+        // Skip empty lines and comments; block scalar contents and the rest
+        // of multi-line flow nodes say nothing about the indentation width
+        // either
         let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
+        if trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || in_block_scalar[line_idx]
+            || continuations[line_idx].is_some()
+        {
             continue;
         }
 
@@ -215,7 +1042,7 @@ fn detect_indent_size(context: &LintContext) -> usize {
             continue;
         }
 
-        let current_indent = line.len() - line.trim_start().len();
+        let mut current_indent = line.len() - line.trim_start().len();
 
         if current_indent > prev_indent && prev_indent == 0 {
             // First indentation level
@@ -226,18 +1053,19 @@ fn detect_indent_size(context: &LintContext) -> usize {
             indents.push(diff);
         }
 
+        // Keys of a mapping inside a list item are aligned after `- `, which
+        // says nothing about the indentation width
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            current_indent += trimmed.len() - item.trim_start().len();
+        }
+
         if current_indent > 0 {
             prev_indent = current_indent;
         }
     }
 
-    // Find the most common indent size (likely 2 or 4)
-    if indents.is_empty() {
-        return 2; // Default to 2 if we can't detect
-    }
-
     // Return the smallest non-zero indent (likely the base indent)
-    *indents.iter().min().unwrap_or(&2)
+    indents.into_iter().min()
 }
 
 #[cfg(test)]
@@ -316,6 +1144,296 @@ mod tests {
         assert!(problems.is_empty());
     }
 
+    #[test]
+    fn test_indent_sequences_true() {
+        let rule = IndentationRule::with_spaces(2);
+
+        let yaml = "list:\n  - a\n  - b\n";
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+        assert!(problems.is_empty());
+
+        let yaml = "list:\n- a\n- b\n";
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+        assert_eq!(problems.len(), 1);
+        assert_eq!((problems[0].line, problems[0].column), (2, 1));
+        assert_eq!(
+            problems[0].message,
+            "wrong indentation: expected 2 but got 0"
+        );
+    }
+
+    #[test]
+    fn test_indent_sequences_over_indented() {
+        let yaml = "list:\n    - a\n    - b\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = IndentationRule::with_spaces(2);
+        let problems = rule.check(&context);
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!((problems[0].line, problems[0].column), (2, 5));
+        assert!(problems[0].message.contains("expected 2 but got 4"));
+    }
+
+    #[test]
+    fn test_indent_sequences_false() {
+        let rule = IndentationRule::with_config(
+            IndentSpaces::Fixed(2),
+            IndentSequences::NotIndented,
+            false,
+        );
+
+        let yaml = "list:\n- a\n- b\n";
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+        assert!(problems.is_empty());
+
+        let yaml = "list:\n  - a\n  - b\n";
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+        assert_eq!(problems.len(), 1);
+        assert_eq!((problems[0].line, problems[0].column), (2, 3));
+        assert!(problems[0].message.contains("expected 0 but got 2"));
+    }
+
+    #[test]
+    fn test_indent_sequences_whatever() {
+        let yaml = "a:\n  - 1\nb:\n- 2\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule =
+            IndentationRule::with_config(IndentSpaces::Fixed(2), IndentSequences::Whatever, false);
+        let problems = rule.check(&context);
+
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_indent_sequences_consistent() {
+        let rule = IndentationRule::with_config(
+            IndentSpaces::Fixed(2),
+            IndentSequences::Consistent,
+            false,
+        );
+
+        let yaml = "a:\n- 1\nb:\n- 2\n";
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+        assert!(problems.is_empty());
+
+        let yaml = "a:\n  - 1\nb:\n- 2\n";
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 4);
+        assert!(problems[0].message.contains("expected 2 but got 0"));
+    }
+
+    #[test]
+    fn test_indent_sequences_under_list_item_key() {
+        let yaml = "- key:\n    - a\n- key:\n  - b\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = IndentationRule::with_spaces(2);
+        let problems = rule.check(&context);
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 4);
+        assert!(problems[0].message.contains("expected 4 but got 2"));
+    }
+
+    #[test]
+    fn test_multi_line_strings_skipped_by_default() {
+        let yaml = "key: |\n  text\n     odd indent\n\n  more\nnext: value\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule = IndentationRule::with_spaces(2);
+        let problems = rule.check(&context);
+
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_flow_and_quoted_continuations_skipped() {
+        let yaml = "a: 1\nb:\n  x: 1\nc: {x: 1,\n   y: 2}\nd: [a,\n     b]\ne: \"multi\n   line\"\nf: 'it''s\n   odd'\n";
+        let context = LintContext::new(yaml.to_string());
+
+        assert!(IndentationRule::new().check(&context).is_empty());
+        assert!(IndentationRule::with_spaces(2).check(&context).is_empty());
+        assert_eq!(detect_indent_size(&context), 2);
+    }
+
+    #[test]
+    fn test_check_multi_line_strings() {
+        let yaml = "key: >-\n    text\n    more\nlist:\n  - |\n      a\n     b\n";
+        let context = LintContext::new(yaml.to_string());
+        let rule =
+            IndentationRule::with_config(IndentSpaces::Fixed(2), IndentSequences::Indented, true);
+        let problems = rule.check(&context);
+
+        assert_eq!(problems.len(), 3);
+        assert_eq!((problems[0].line, problems[0].column), (2, 5));
+        assert!(problems[0].message.contains("expected 2 but got 4"));
+        assert_eq!(problems[1].line, 3);
+        assert_eq!((problems[2].line, problems[2].column), (7, 6));
+        assert!(problems[2].message.contains("expected 6 but got 5"));
+    }
+
+    /// Multi-line scalars with the problems yamllint reports for them when
+    /// `check-multi-line-strings` is on, as (line, column). Its
+    /// `check_scalar_indentation` expects plain scalar lines at the column
+    /// where the scalar starts, quoted scalar lines one column after the
+    /// opening quote, and block scalar contents one level deeper than the
+    /// parent. With the option off none of these is a problem.
+    const YAMLLINT_MULTI_LINE_CASES: &[(&str, &[(usize, usize)])] = &[
+        // Plain scalars
+        ("- multi\n  line\n", &[]),
+        ("- multi\n   line\n", &[(2, 4)]),
+        ("a key: multi\n       line\n", &[]),
+        ("a key: multi\n  line\n", &[(2, 3)]),
+        ("a key: multi\n        line\n", &[(2, 9)]),
+        ("a key:\n  multi\n  line\n", &[]),
+        ("a key:\n  multi\n   line\n", &[(3, 4)]),
+        (
+            "- C code: void main() {\n                printf(\"foo\");\n         }\n",
+            &[(2, 17), (3, 10)],
+        ),
+        (
+            "- C code:\n    void main() {\n        printf(\"foo\");\n    }\n",
+            &[(3, 9)],
+        ),
+        ("a: multi\n\n   line\nb: 1\n", &[]),
+        // Quoted scalars
+        ("\"multi\n line\"\n", &[]),
+        ("\"multi\nline\"\n", &[(2, 1)]),
+        ("\"multi\n  line\"\n", &[(2, 3)]),
+        ("- \"multi\n   line\"\n", &[]),
+        ("- \"multi\n  line\"\n", &[(2, 3)]),
+        ("- \"multi\n    line\"\n", &[(2, 5)]),
+        ("a key: \"multi\n        line\"\n", &[]),
+        ("a key: \"multi\n  line\"\n", &[(2, 3)]),
+        ("a key: \"multi\n       line\"\n", &[(2, 8)]),
+        ("a key: \"multi\n         line\"\n", &[(2, 10)]),
+        ("a key:\n  \"multi\n   line\"\n", &[]),
+        ("a key:\n  \"multi\n  line\"\n", &[(3, 3)]),
+        ("a key:\n  \"multi\n    line\"\n", &[(3, 5)]),
+        ("a key: 'it''s\n        multi'\n", &[]),
+        (
+            "- jinja2: \"{% if ansible is defined %}\n             {{ ansible }}\n           {% else %}\n             {{ chef }}\n           {% endif %}\"\n",
+            &[(2, 14), (4, 14)],
+        ),
+        // Block scalars
+        ("a key: |\n  text\n", &[]),
+        ("a key: |\n   text\n", &[(2, 4)]),
+        ("- |\n    text\n", &[]),
+        ("- |\n   text\n", &[(2, 4)]),
+        ("- key: >\n    text\n", &[]),
+        ("- key: >\n     text\n", &[(2, 6)]),
+    ];
+
+    #[test]
+    fn test_multi_line_strings_match_yamllint() {
+        for (yaml, expected) in YAMLLINT_MULTI_LINE_CASES {
+            let context = LintContext::new(yaml.to_string());
+
+            let rule = IndentationRule::with_spaces(2);
+            assert!(rule.check(&context).is_empty(), "{:?}", yaml);
+
+            let rule = IndentationRule::with_config(
+                IndentSpaces::Fixed(2),
+                IndentSequences::Indented,
+                true,
+            );
+            let problems = rule
+                .check(&context)
+                .iter()
+                .map(|problem| (problem.line, problem.column))
+                .collect::<Vec<_>>();
+            assert_eq!(problems, *expected, "{:?}", yaml);
+        }
+    }
+
+    #[test]
+    fn test_fix_reindents_to_configured_spaces() {
+        let yaml =
+            "root:\n    key: value\n    list:\n        - a\n        - b: 1\n          c: 2\n";
+        let rule = IndentationRule::with_spaces(2);
+        let problem = LintProblem::new(2, 1, "wrong indentation", "indentation", LintLevel::Error);
+
+        let fixed = rule.fix(yaml, &problem).unwrap();
+        assert_eq!(
+            fixed,
+            "root:\n  key: value\n  list:\n    - a\n    - b: 1\n      c: 2\n"
+        );
+        assert!(rule.check(&LintContext::new(fixed)).is_empty());
+    }
+
+    #[test]
+    fn test_fix_indent_sequences() {
+        let yaml = "list:\n- a\n- b\nother: value\n";
+        let rule = IndentationRule::with_spaces(2);
+        let problem = LintProblem::new(2, 1, "wrong indentation", "indentation", LintLevel::Error);
+
+        let fixed = rule.fix(yaml, &problem).unwrap();
+        assert_eq!(fixed, "list:\n  - a\n  - b\nother: value\n");
+
+        let rule = IndentationRule::with_config(
+            IndentSpaces::Fixed(2),
+            IndentSequences::NotIndented,
+            false,
+        );
+        assert_eq!(rule.fix(&fixed, &problem).unwrap(), yaml);
+    }
+
+    #[test]
+    fn test_fix_preserves_block_scalars_and_flow_collections() {
+        let yaml =
+            "key:\n    text: |\n      line one\n        line two\n    flow: [a,\n           b]\n";
+        let rule = IndentationRule::with_spaces(2);
+        let problem = LintProblem::new(2, 1, "wrong indentation", "indentation", LintLevel::Error);
+
+        let fixed = rule.fix(yaml, &problem).unwrap();
+        assert_eq!(
+            fixed,
+            "key:\n  text: |\n    line one\n      line two\n  flow: [a,\n         b]\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_preserves_crlf_line_endings() {
+        let yaml = "a:\r\n   b: 1\r\n   c:\r\n      - x\r\n";
+        let rule = IndentationRule::with_spaces(2);
+        let problem = LintProblem::new(2, 1, "wrong indentation", "indentation", LintLevel::Error);
+
+        assert_eq!(
+            rule.fix(yaml, &problem).unwrap(),
+            "a:\r\n  b: 1\r\n  c:\r\n    - x\r\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_aligns_comments_with_next_line() {
+        let yaml = "key:\n    # comment\n    sub: value\n";
+        let rule = IndentationRule::with_spaces(2);
+        let problem = LintProblem::new(3, 1, "wrong indentation", "indentation", LintLevel::Error);
+
+        assert_eq!(
+            rule.fix(yaml, &problem).unwrap(),
+            "key:\n  # comment\n  sub: value\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_no_change() {
+        let yaml = "key:\n  sub: value\n";
+        let rule = IndentationRule::with_spaces(2);
+        let problem = LintProblem::new(2, 1, "wrong indentation", "indentation", LintLevel::Error);
+
+        assert!(rule.fix(yaml, &problem).is_none());
+    }
+
+    #[test]
+    fn test_block_scalar_header_detection() {
+        assert!(is_block_scalar_header("|"));
+        assert!(is_block_scalar_header(">-"));
+        assert!(is_block_scalar_header("|2+  # comment"));
+        assert!(is_block_scalar_header("!!str |"));
+        assert!(!is_block_scalar_header("|text"));
+        assert!(!is_block_scalar_header("value"));
+    }
+
     #[test]
     fn test_detect_indent_size_2() {
         let yaml = "key:\n  nested: value\n  nested2:\n    deep: value\n";
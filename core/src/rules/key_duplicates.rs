@@ -1,7 +1,7 @@
 //! Key duplicates rule - detects duplicate keys in YAML mappings
 
 use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::rules::{LintContext, Rule, RuleLevel, RuleMetadata};
 use std::collections::HashSet;
 use yaml_rust2::YamlLoader;
 
@@ -14,6 +14,18 @@ impl Rule for KeyDuplicatesRule {
         "key-duplicates"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "key-duplicates",
+            description: "Forbids repeating a key within the same mapping.",
+            rationale: "Most parsers silently keep only the last value of a repeated key.",
+            fixable: false,
+            options: &[],
+            bad_example: "name: app\nreplicas: 1\nname: web\n",
+            good_example: "name: app\nreplicas: 1\nalias: web\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
 
@@ -98,13 +110,20 @@ fn check_duplicate_keys_in_lines(context: &LintContext) -> Vec<LintProblem> {
                 let current_level = key_tracker.last_mut().unwrap();
 
                 if current_level.1.contains(&key) {
-                    problems.push(LintProblem::new(
-                        line_idx + 1,
-                        1,
-                        format!("found duplicate key \"{}\"", key),
-                        "key-duplicates",
-                        LintLevel::Error,
-                    ));
+                    problems.push(
+                        LintProblem::new(
+                            line_idx + 1,
+                            indent + 1,
+                            format!("found duplicate key \"{}\"", key),
+                            "key-duplicates",
+                            LintLevel::Error,
+                        )
+                        .with_range(
+                            context,
+                            line_idx + 1,
+                            indent + key_part.len() + 1,
+                        ),
+                    );
                 } else {
                     current_level.1.insert(key.clone());
                 }
@@ -223,6 +242,9 @@ mod tests {
 
         assert_eq!(problems.len(), 1);
         assert_eq!(problems[0].line, 3);
+        assert_eq!(problems[0].column, 3);
+        assert_eq!(problems[0].end_column, Some(6));
+        assert_eq!(&yaml[problems[0].span.clone().unwrap()], "key");
     }
 
     #[test]
@@ -1,7 +1,7 @@
 //! Line length rule - enforces maximum line length
 
 use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::rules::{LintContext, OptionType, Rule, RuleLevel, RuleMetadata, RuleOption};
 
 /// Rule that enforces maximum line length
 #[derive(Debug)]
@@ -33,6 +33,23 @@ impl Rule for LineLengthRule {
         "line-length"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "line-length",
+            description: "Limits the length of lines.",
+            rationale: "Long lines are hard to read and to review side by side.",
+            fixable: false,
+            options: &[RuleOption {
+                name: "max",
+                value_type: OptionType::Integer,
+                default: Some("80"),
+                description: "Maximum number of characters on a line",
+            }],
+            bad_example: "description: this value goes on well past the eighty characters that a line may have\n",
+            good_example: "description: >\n  this value goes on well past the eighty characters\n  that a line may have\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
 
@@ -41,13 +58,16 @@ impl Rule for LineLengthRule {
             let line_length = line.len();
 
             if line_length > self.max {
-                problems.push(LintProblem::new(
-                    line_idx + 1, // 1-indexed
-                    self.max + 1, // Column where it exceeds
-                    format!("line too long ({} > {} characters)", line_length, self.max),
-                    self.name(),
-                    LintLevel::Error,
-                ));
+                problems.push(
+                    LintProblem::new(
+                        line_idx + 1, // 1-indexed
+                        self.max + 1, // Column where it exceeds
+                        format!("line too long ({} > {} characters)", line_length, self.max),
+                        self.name(),
+                        LintLevel::Error,
+                    )
+                    .with_range(context, line_idx + 1, line_length + 1),
+                );
             }
         }
 
@@ -87,6 +107,8 @@ mod tests {
         assert_eq!(problems[0].line, 1);
         assert_eq!(problems[0].column, 81); // Column where it exceeds
         assert!(problems[0].message.contains("81 > 80"));
+        assert_eq!(problems[0].end_column, Some(82));
+        assert_eq!(problems[0].span, Some(80..81));
     }
 
     #[test]
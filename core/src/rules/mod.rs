@@ -26,6 +26,104 @@ pub enum RuleLevel {
     Error,
 }
 
+impl std::fmt::Display for RuleLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleLevel::Disable => write!(f, "disable"),
+            RuleLevel::Warning => write!(f, "warning"),
+            RuleLevel::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Documentation of a rule, for generated docs and editor integrations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMetadata {
+    /// Name of the rule, as used in config files
+    pub name: &'static str,
+    /// What the rule checks, in one sentence
+    pub description: &'static str,
+    /// Why the rule is worth enabling
+    pub rationale: &'static str,
+    /// Whether `--fix` can fix the rule's problems, with some options at least
+    pub fixable: bool,
+    /// Options accepted besides `level`
+    pub options: &'static [RuleOption],
+    /// YAML the rule reports problems for
+    pub bad_example: &'static str,
+    /// The same YAML, without the problems
+    pub good_example: &'static str,
+}
+
+impl RuleMetadata {
+    /// URL of the rule's documentation
+    pub fn doc_url(&self) -> String {
+        doc_url(self.name)
+    }
+}
+
+/// URL of the documentation of the rule named `name`
+///
+/// Rules are documented under headings named after them, so the URL stays
+/// valid as the documentation changes.
+pub fn doc_url(name: &str) -> String {
+    format!(
+        "{}/blob/main/docs/RULES.md#{}",
+        env!("CARGO_PKG_REPOSITORY"),
+        name
+    )
+}
+
+/// An option of a rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleOption {
+    /// Key of the option in the rule's config
+    pub name: &'static str,
+    /// Values the option accepts
+    pub value_type: OptionType,
+    /// Default value in YAML syntax, `None` if unset by default
+    pub default: Option<&'static str>,
+    /// What the option does
+    pub description: &'static str,
+}
+
+/// Values a rule option accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionType {
+    /// `true` or `false`
+    Bool,
+    /// A non-negative integer
+    Integer,
+    /// A list of strings
+    StringList,
+    /// One of the listed strings
+    Choice(&'static [&'static str]),
+    /// A non-negative integer or one of the listed strings
+    IntegerOr(&'static [&'static str]),
+    /// `true`, `false` or one of the listed strings
+    BoolOr(&'static [&'static str]),
+}
+
+impl std::fmt::Display for OptionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let choices = |choices: &[&str]| {
+            choices
+                .iter()
+                .map(|choice| format!("\"{}\"", choice))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        };
+        match self {
+            OptionType::Bool => write!(f, "bool"),
+            OptionType::Integer => write!(f, "int"),
+            OptionType::StringList => write!(f, "list of strings"),
+            OptionType::Choice(values) => write!(f, "{}", choices(values)),
+            OptionType::IntegerOr(values) => write!(f, "int | {}", choices(values)),
+            OptionType::BoolOr(values) => write!(f, "bool | {}", choices(values)),
+        }
+    }
+}
+
 /// Context provided to rules during checking
 #[derive(Debug)]
 pub struct LintContext {
@@ -33,13 +131,35 @@ pub struct LintContext {
     pub content: String,
     /// Lines of the content (for convenience)
     pub lines: Vec<String>,
+    /// Byte offset at which each line starts in `content`
+    line_offsets: Vec<usize>,
 }
 
 impl LintContext {
     /// Create a new lint context from content
     pub fn new(content: String) -> Self {
         let lines = content.lines().map(|s| s.to_string()).collect();
-        Self { content, lines }
+        let line_offsets = content
+            .split_inclusive('\n')
+            .scan(0, |offset, line| {
+                let start = *offset;
+                *offset += line.len();
+                Some(start)
+            })
+            .collect();
+        Self {
+            content,
+            lines,
+            line_offsets,
+        }
+    }
+
+    /// Byte offset of a 0-based line index and byte column within that line.
+    /// Indexes past the last line map to the end of the content.
+    pub fn offset(&self, line_idx: usize, col_idx: usize) -> usize {
+        self.line_offsets
+            .get(line_idx)
+            .map_or(self.content.len(), |start| start + col_idx)
     }
 }
 
@@ -48,6 +168,22 @@ pub trait Rule: Send + Sync + std::fmt::Debug {
     /// Returns the name of this rule (e.g., "trailing-spaces")
     fn name(&self) -> &'static str;
 
+    /// Returns the documentation of this rule
+    ///
+    /// The default only fills in the name and whether the rule is fixable,
+    /// so rules written before metadata existed keep compiling.
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: self.name(),
+            description: "",
+            rationale: "",
+            fixable: self.is_fixable(),
+            options: &[],
+            bad_example: "",
+            good_example: "",
+        }
+    }
+
     /// Check the given context for problems
     fn check(&self, context: &LintContext) -> Vec<LintProblem>;
 
@@ -63,8 +199,13 @@ pub trait Rule: Send + Sync + std::fmt::Debug {
 
     /// Fix the content for a specific problem
     /// Returns the fixed content if the fix was successful, None otherwise
-    fn fix(&self, _content: &str, _problem: &LintProblem) -> Option<String> {
-        None
+    ///
+    /// Rules should prefer attaching a [`Fix`](crate::problem::Fix) to the
+    /// problems they report, which this default applies. Overriding this is
+    /// meant for fixes that rewrite the whole file; the fixer applies those
+    /// one at a time and re-lints in between.
+    fn fix(&self, content: &str, problem: &LintProblem) -> Option<String> {
+        problem.fix.as_ref().map(|fix| fix.apply(content))
     }
 }
 
@@ -154,6 +295,10 @@ impl RuleRegistry {
             }));
         }
 
+        // Drop problems disabled by `# yamllint` comments
+        let directives = crate::directives::Directives::parse(context);
+        problems.retain(|problem| !directives.is_disabled(&problem.rule, problem.line));
+
         // Sort problems by line/column
         problems.sort();
         problems
@@ -184,4 +329,59 @@ mod tests {
         registry.set_level(&rule_name, RuleLevel::Warning);
         assert_eq!(registry.get_level(&rule_name), Some(RuleLevel::Warning));
     }
+
+    #[test]
+    fn test_metadata_examples() {
+        let registry = RuleRegistry::with_defaults();
+        // document-start checks nothing until configured
+        let document_start = document_start::DocumentStartRule::required();
+
+        for name in registry.rule_names() {
+            let rule = match name {
+                "document-start" => &document_start,
+                _ => registry.get(name).unwrap(),
+            };
+            let metadata = rule.metadata();
+            let check = |example: &str| rule.check(&LintContext::new(example.to_string()));
+
+            assert_eq!(metadata.name, name);
+            assert!(!check(metadata.bad_example).is_empty(), "{}", name);
+            assert!(check(metadata.good_example).is_empty(), "{}", name);
+            assert!(
+                metadata
+                    .doc_url()
+                    .ends_with(&format!("/docs/RULES.md#{}", name))
+            );
+        }
+    }
+
+    #[test]
+    fn test_option_type_display() {
+        assert_eq!(OptionType::Integer.to_string(), "int");
+        assert_eq!(
+            OptionType::BoolOr(&["whatever", "consistent"]).to_string(),
+            r#"bool | "whatever" | "consistent""#
+        );
+    }
+
+    #[derive(Debug)]
+    struct UndocumentedRule;
+
+    impl Rule for UndocumentedRule {
+        fn name(&self) -> &'static str {
+            "undocumented"
+        }
+
+        fn check(&self, _context: &LintContext) -> Vec<LintProblem> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_default_metadata() {
+        let metadata = UndocumentedRule.metadata();
+        assert_eq!(metadata.name, "undocumented");
+        assert!(!metadata.fixable);
+        assert!(metadata.options.is_empty());
+    }
 }
@@ -1,7 +1,7 @@
 //! New line at end of file rule - ensures files end with a newline
 
-use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::problem::{Fix, LintLevel, LintProblem, TextEdit};
+use crate::rules::{LintContext, Rule, RuleLevel, RuleMetadata};
 
 /// Rule that checks if a file ends with a newline character
 ///
@@ -15,6 +15,18 @@ impl Rule for NewLineAtEndOfFileRule {
         "new-line-at-end-of-file"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "new-line-at-end-of-file",
+            description: "Requires files to end with a newline.",
+            rationale: "POSIX tools expect text files to end with a newline, and appending to a file without one changes its last line in diffs.",
+            fixable: true,
+            options: &[],
+            bad_example: "key: value",
+            good_example: "key: value\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
 
@@ -28,13 +40,19 @@ impl Rule for NewLineAtEndOfFileRule {
             let line_count = context.lines.len();
             let last_line_len = context.lines.last().map(|l| l.len()).unwrap_or(0);
 
-            problems.push(LintProblem::new(
-                line_count,
-                last_line_len + 1,
-                "no new line character at the end of file",
-                self.name(),
-                LintLevel::Error,
-            ));
+            let fix = Fix::new(vec![TextEdit::insert(context.content.len(), "\n")]);
+
+            problems.push(
+                LintProblem::new(
+                    line_count,
+                    last_line_len + 1,
+                    "no new line character at the end of file",
+                    self.name(),
+                    LintLevel::Error,
+                )
+                .with_range(context, line_count, last_line_len + 1)
+                .with_fix(fix),
+            );
         }
 
         problems
@@ -47,15 +65,6 @@ impl Rule for NewLineAtEndOfFileRule {
     fn is_fixable(&self) -> bool {
         true
     }
-
-    fn fix(&self, content: &str, _problem: &LintProblem) -> Option<String> {
-        // Simply add a newline at the end if missing
-        if content.is_empty() || content.ends_with('\n') {
-            return None; // Already valid
-        }
-
-        Some(format!("{}\n", content))
-    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 //! Trailing spaces rule - detects whitespace at line endings
 
-use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::problem::{Fix, LintLevel, LintProblem, TextEdit};
+use crate::rules::{LintContext, Rule, RuleLevel, RuleMetadata};
 
 /// Rule that detects trailing spaces at the end of lines
 #[derive(Debug)]
@@ -12,6 +12,18 @@ impl Rule for TrailingSpacesRule {
         "trailing-spaces"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "trailing-spaces",
+            description: "Forbids spaces and tabs at the end of lines.",
+            rationale: "Trailing whitespace is invisible and causes noise in diffs.",
+            fixable: true,
+            options: &[],
+            bad_example: "key: value  \n",
+            good_example: "key: value\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
 
@@ -21,13 +33,21 @@ impl Rule for TrailingSpacesRule {
                 let trimmed_len = line.trim_end().len();
                 let column = trimmed_len + 1; // 1-indexed
 
-                problems.push(LintProblem::new(
-                    line_idx + 1, // 1-indexed line number
-                    column,
-                    "trailing spaces",
-                    self.name(),
-                    LintLevel::Error,
-                ));
+                let fix = Fix::new(vec![TextEdit::delete(
+                    context.offset(line_idx, trimmed_len)..context.offset(line_idx, line.len()),
+                )]);
+
+                problems.push(
+                    LintProblem::new(
+                        line_idx + 1, // 1-indexed line number
+                        column,
+                        "trailing spaces",
+                        self.name(),
+                        LintLevel::Error,
+                    )
+                    .with_range(context, line_idx + 1, line.len() + 1)
+                    .with_fix(fix),
+                );
             }
         }
 
@@ -41,29 +61,6 @@ impl Rule for TrailingSpacesRule {
     fn is_fixable(&self) -> bool {
         true
     }
-
-    fn fix(&self, content: &str, _problem: &LintProblem) -> Option<String> {
-        // Check if there's anything to fix to avoid unnecessary string allocation
-        if !content
-            .lines()
-            .any(|line| line.ends_with(' ') || line.ends_with('\t'))
-        {
-            return None;
-        }
-
-        let mut result = content
-            .lines()
-            .map(|line| line.trim_end())
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        // Preserve original trailing newline if it existed
-        if content.ends_with('\n') {
-            result.push('\n');
-        }
-
-        Some(result)
-    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,23 @@
 //! Truthy rule - restricts boolean representations to avoid YAML 1.1 vs 1.2 ambiguities
 
-use crate::problem::{LintLevel, LintProblem};
-use crate::rules::{LintContext, Rule, RuleLevel};
+use crate::problem::{Fix, LintLevel, LintProblem, TextEdit};
+use crate::rules::{LintContext, OptionType, Rule, RuleLevel, RuleMetadata, RuleOption};
 
 /// YAML 1.1 truthy values (case-insensitive)
 const YAML_11_TRUTHY_VALUES: &[&str] = &["y", "yes", "on", "true", "n", "no", "off", "false"];
 
+/// YAML 1.1 values that mean `true` (case-insensitive)
+const YAML_11_TRUE_VALUES: &[&str] = &["y", "yes", "on", "true"];
+
+/// How flagged values are rewritten by `--fix`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TruthyFixStyle {
+    /// Replace with the first allowed value of the same polarity (`yes` -> `true`)
+    Canonical,
+    /// Quote the value so it is read as a string (`yes` -> `"yes"`)
+    Quote,
+}
+
 /// Rule that restricts boolean representations
 ///
 /// YAML 1.1 treats many values as booleans (yes, no, on, off, y, n)
@@ -17,6 +29,8 @@ pub struct TruthyRule {
     allowed_values: Vec<String>,
     /// Also check mapping keys (default: false)
     check_keys: bool,
+    /// Rewrite flagged values on `--fix` (default: not fixable)
+    fix_style: Option<TruthyFixStyle>,
 }
 
 impl TruthyRule {
@@ -25,14 +39,20 @@ impl TruthyRule {
         Self {
             allowed_values: vec!["true".to_string(), "false".to_string()],
             check_keys: false,
+            fix_style: None,
         }
     }
 
     /// Create a new rule with custom settings
-    pub fn with_config(allowed_values: Vec<String>, check_keys: bool) -> Self {
+    pub fn with_config(
+        allowed_values: Vec<String>,
+        check_keys: bool,
+        fix_style: Option<TruthyFixStyle>,
+    ) -> Self {
         Self {
             allowed_values,
             check_keys,
+            fix_style,
         }
     }
 
@@ -50,6 +70,36 @@ impl TruthyRule {
             .any(|allowed| allowed.eq_ignore_ascii_case(value))
     }
 
+    /// Check if a YAML 1.1 truthy value means `true`
+    fn is_true_value(value: &str) -> bool {
+        YAML_11_TRUE_VALUES
+            .iter()
+            .any(|&v| v.eq_ignore_ascii_case(value))
+    }
+
+    /// Find the first allowed value with the same polarity as `value`
+    fn canonical_value(&self, value: &str) -> Option<&str> {
+        let polarity = Self::is_true_value(value);
+        self.allowed_values
+            .iter()
+            .find(|allowed| {
+                Self::is_truthy_value(allowed) && Self::is_true_value(allowed) == polarity
+            })
+            .map(|allowed| allowed.as_str())
+    }
+
+    /// Fix replacing `token`, found at `col_idx` of a line, with `replacement`
+    fn replace_token(
+        context: &LintContext,
+        line_idx: usize,
+        col_idx: usize,
+        token: &str,
+        replacement: String,
+    ) -> Fix {
+        let start = context.offset(line_idx, col_idx);
+        Fix::new(vec![TextEdit::new(start..start + token.len(), replacement)])
+    }
+
     /// Check if a value is quoted (starts and ends with quotes)
     fn is_quoted(value: &str) -> bool {
         (value.starts_with('"') && value.ends_with('"'))
@@ -75,7 +125,8 @@ impl TruthyRule {
             // If there's no colon, it's a simple list value
             if !after_hyphen.contains(':') {
                 let value_start = line.find(after_hyphen)?;
-                return Some(("", after_hyphen, value_start + 1));
+                let value = Self::strip_inline_comment(after_hyphen);
+                return Some(("", value, value_start + 1));
             }
         }
 
@@ -166,6 +217,37 @@ impl Rule for TruthyRule {
         "truthy"
     }
 
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            name: "truthy",
+            description: "Restricts the values read as booleans to an allowed set, `true` and `false` by default.",
+            rationale: "YAML 1.1 parsers read `yes`, `no`, `on`, `off`, `y` and `n` as booleans while YAML 1.2 parsers read them as strings.",
+            fixable: true,
+            options: &[
+                RuleOption {
+                    name: "allowed-values",
+                    value_type: OptionType::StringList,
+                    default: Some("[\"true\", \"false\"]"),
+                    description: "Boolean values that are allowed",
+                },
+                RuleOption {
+                    name: "check-keys",
+                    value_type: OptionType::Bool,
+                    default: Some("false"),
+                    description: "Whether mapping keys are checked too",
+                },
+                RuleOption {
+                    name: "fix-style",
+                    value_type: OptionType::Choice(&["canonical", "quote"]),
+                    default: None,
+                    description: "How `--fix` rewrites values: `canonical` uses an allowed value, `quote` quotes them; unset, values are not fixed",
+                },
+            ],
+            bad_example: "enabled: yes\ncountry: NO\n",
+            good_example: "enabled: true\ncountry: \"NO\"\n",
+        }
+    }
+
     fn check(&self, context: &LintContext) -> Vec<LintProblem> {
         let mut problems = Vec::new();
 
@@ -178,7 +260,7 @@ impl Rule for TruthyRule {
                     && Self::is_truthy_value(value)
                     && !self.is_allowed(value)
                 {
-                    problems.push(LintProblem::new(
+                    let problem = LintProblem::new(
                         line_num,
                         value_col,
                         format!(
@@ -187,7 +269,25 @@ impl Rule for TruthyRule {
                         ),
                         self.name(),
                         LintLevel::Error,
-                    ));
+                    )
+                    .with_range(context, line_num, value_col + value.len());
+                    let fix = self.fix_style.and_then(|fix_style| {
+                        let replacement = match fix_style {
+                            TruthyFixStyle::Canonical => self.canonical_value(value)?.to_string(),
+                            TruthyFixStyle::Quote => format!("\"{}\"", value),
+                        };
+                        Some(Self::replace_token(
+                            context,
+                            line_idx,
+                            value_col - 1,
+                            value,
+                            replacement,
+                        ))
+                    });
+                    problems.push(match fix {
+                        Some(fix) => problem.with_fix(fix),
+                        None => problem,
+                    });
                 }
 
                 // Check key if configured
@@ -198,13 +298,26 @@ impl Rule for TruthyRule {
                     && !self.is_allowed(key)
                 {
                     let key_col = line.find(key).map(|p| p + 1).unwrap_or(1);
-                    problems.push(LintProblem::new(
+                    let problem = LintProblem::new(
                         line_num,
                         key_col,
                         format!("truthy value \"{}\" used as key should be quoted", key),
                         self.name(),
                         LintLevel::Error,
-                    ));
+                    )
+                    .with_range(context, line_num, key_col + key.len());
+                    // Keys are always quoted; only values are candidates for canonical booleans
+                    problems.push(if self.fix_style.is_some() {
+                        problem.with_fix(Self::replace_token(
+                            context,
+                            line_idx,
+                            key_col - 1,
+                            key,
+                            format!("\"{}\"", key),
+                        ))
+                    } else {
+                        problem
+                    });
                 }
             }
         }
@@ -215,11 +328,16 @@ impl Rule for TruthyRule {
     fn default_level(&self) -> RuleLevel {
         RuleLevel::Warning
     }
+
+    fn is_fixable(&self) -> bool {
+        self.fix_style.is_some()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixer::fix_with;
 
     #[test]
     fn test_valid_booleans() {
@@ -297,6 +415,7 @@ mod tests {
                 "no".to_string(),
             ],
             false,
+            None,
         );
         let problems = rule.check(&context);
 
@@ -317,7 +436,8 @@ mod tests {
     fn test_check_keys_enabled() {
         let yaml = "yes: value\n";
         let context = LintContext::new(yaml.to_string());
-        let rule = TruthyRule::with_config(vec!["true".to_string(), "false".to_string()], true);
+        let rule =
+            TruthyRule::with_config(vec!["true".to_string(), "false".to_string()], true, None);
         let problems = rule.check(&context);
 
         assert_eq!(problems.len(), 1);
@@ -373,4 +493,78 @@ mod tests {
 
         assert!(problems.is_empty());
     }
+
+    #[test]
+    fn test_not_fixable_by_default() {
+        let rule = TruthyRule::new();
+        let problem = LintProblem::new(1, 10, "truthy", "truthy", LintLevel::Error);
+
+        assert!(!rule.is_fixable());
+        assert!(rule.fix("enabled: yes\n", &problem).is_none());
+    }
+
+    #[test]
+    fn test_fix_canonical() {
+        let yaml = "enabled: yes\nitems:\n  - Off\n  - on  # comment\n";
+        let rule = TruthyRule::with_config(
+            vec!["true".to_string(), "false".to_string()],
+            false,
+            Some(TruthyFixStyle::Canonical),
+        );
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+        assert_eq!(problems.len(), 3);
+
+        let fixed = rule.fix(yaml, &problems[0]).unwrap();
+        assert_eq!(fixed, "enabled: true\nitems:\n  - Off\n  - on  # comment\n");
+        let fixed = fix_with(Box::new(rule), yaml).unwrap();
+        assert_eq!(
+            fixed,
+            "enabled: true\nitems:\n  - false\n  - true  # comment\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_canonical_uses_first_allowed_value() {
+        let yaml = "enabled: on\n";
+        let rule = TruthyRule::with_config(
+            vec!["no".to_string(), "yes".to_string(), "true".to_string()],
+            false,
+            Some(TruthyFixStyle::Canonical),
+        );
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+
+        assert_eq!(rule.fix(yaml, &problems[0]).unwrap(), "enabled: yes\n");
+    }
+
+    #[test]
+    fn test_fix_canonical_without_matching_polarity() {
+        let yaml = "enabled: off\n";
+        let rule = TruthyRule::with_config(
+            vec!["true".to_string()],
+            false,
+            Some(TruthyFixStyle::Canonical),
+        );
+        let problems = rule.check(&LintContext::new(yaml.to_string()));
+
+        assert!(rule.fix(yaml, &problems[0]).is_none());
+    }
+
+    #[test]
+    fn test_fix_quote() {
+        let yaml = "country: NO\nyes: value\n";
+        let rule = || {
+            TruthyRule::with_config(
+                vec!["true".to_string(), "false".to_string()],
+                true,
+                Some(TruthyFixStyle::Quote),
+            )
+        };
+        let problems = rule().check(&LintContext::new(yaml.to_string()));
+        assert_eq!(problems.len(), 2);
+        assert!(problems.iter().all(|problem| problem.fix.is_some()));
+
+        let fixed = fix_with(Box::new(rule()), yaml).unwrap();
+        assert_eq!(fixed, "country: \"NO\"\n\"yes\": value\n");
+        assert!(rule().check(&LintContext::new(fixed)).is_empty());
+    }
 }
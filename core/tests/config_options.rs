@@ -1,6 +1,17 @@
 //! Integration tests for configuration options
 
-use yaml_lint_core::{Config, Linter};
+use std::path::{Path, PathBuf};
+use yaml_lint_core::config::{RuleConfig, RuleOptions};
+use yaml_lint_core::rules::RuleLevel;
+use yaml_lint_core::{Config, Fixer, Linter};
+
+fn extends_fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("tests/fixtures/config/extends")
+        .join(name)
+}
 
 #[test]
 fn test_line_length_custom_max() {
@@ -10,7 +21,7 @@ rules:
     max: 120
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // 100-character line (should pass with max: 120)
     let yaml = format!("key: {}\n", "x".repeat(95));
@@ -39,7 +50,7 @@ rules:
     spaces: 2
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Valid 2-space indentation
     let yaml = "key:\n  subkey: value\n";
@@ -66,7 +77,7 @@ rules:
     spaces: consistent
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Consistent 2-space indentation
     let yaml = "key:\n  sub1: value\n  sub2: value\n";
@@ -85,6 +96,45 @@ rules:
     );
 }
 
+#[test]
+fn test_indentation_sequence_and_multi_line_options() {
+    let config_yaml = r#"
+rules:
+  indentation:
+    spaces: 2
+    indent-sequences: false
+    check-multi-line-strings: true
+"#;
+    let config = Config::load_from_str(config_yaml).unwrap();
+    let linter = Linter::new(config).unwrap();
+
+    // Non-indented sequence with correctly indented block scalar
+    let yaml = "list:\n- a\ntext: |\n  line\n";
+    let problems = linter.lint_string(yaml).unwrap();
+    assert!(problems.is_empty(), "Unexpected problems: {:?}", problems);
+
+    // Indented sequence and over-indented block scalar content
+    let yaml = "list:\n  - a\ntext: |\n    line\n";
+    let problems = linter.lint_string(yaml).unwrap();
+    assert_eq!(problems.len(), 2, "Unexpected problems: {:?}", problems);
+    assert!(problems.iter().all(|p| p.rule == "indentation"));
+}
+
+#[test]
+fn test_indentation_invalid_indent_sequences_error() {
+    let config_yaml = r#"
+rules:
+  indentation:
+    indent-sequences: sometimes
+"#;
+    let result = Config::load_from_str(config_yaml);
+    assert!(
+        result.is_err(),
+        "Expected error for invalid indent-sequences"
+    );
+    assert!(result.unwrap_err().to_string().contains("indent-sequences"));
+}
+
 #[test]
 fn test_colons_custom_spacing() {
     let config_yaml = r#"
@@ -94,7 +144,7 @@ rules:
     max-spaces-after: 2
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Valid: 1 space before, 2 spaces after
     let yaml = "key :  value\n";
@@ -123,7 +173,7 @@ rules:
     max-end: 1
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Valid: 1 empty line at start, 1 in middle, 1 at end
     let yaml = "\nkey1: value1\n\nkey2: value2\n\n";
@@ -150,7 +200,7 @@ rules:
     max-spaces-after: 2
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Valid: 2 spaces after hyphen
     let yaml = "list:\n  -  item\n";
@@ -178,7 +228,7 @@ rules:
     min-spaces-from-content: 3
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Valid: no space after #, 3 spaces before comment
     let yaml = "#Comment\nkey: value   #Comment\n";
@@ -206,7 +256,7 @@ rules:
     check-keys: false
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Valid: yes/no are allowed
     let yaml = "enabled: yes\ndisabled: no\n";
@@ -234,7 +284,7 @@ rules:
     check-keys: true
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Invalid: truthy key (yes) when check-keys is true
     let yaml = "yes: value\n";
@@ -254,7 +304,7 @@ rules:
     present: true
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Valid: document starts with ---
     let yaml = "---\nkey: value\n";
@@ -276,7 +326,7 @@ rules:
     present: false
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Valid: document doesn't start with ---
     let yaml = "key: value\n";
@@ -292,6 +342,40 @@ rules:
     );
 }
 
+#[test]
+fn test_document_start_insert_after_comments_option() {
+    let config_yaml = r#"
+rules:
+  document-start:
+    present: true
+    insert-after-comments: false
+"#;
+    let config = Config::load_from_str(config_yaml).unwrap();
+    let registry = config.create_registry().unwrap();
+    let fixer = Fixer::new(&registry);
+
+    let result = fixer.fix("test.yaml", "# header\nkey: value\n");
+    assert_eq!(
+        result.fixed_content,
+        Some("---\n# header\nkey: value\n".to_string())
+    );
+
+    let config_yaml = r#"
+rules:
+  document-start:
+    present: true
+    insert-after-comments: "yes"
+"#;
+    let result = Config::load_from_str(config_yaml);
+    assert!(result.is_err(), "Expected error for non-boolean value");
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("insert-after-comments")
+    );
+}
+
 #[test]
 fn test_backwards_compatibility_string_levels() {
     let config_yaml = r#"
@@ -301,7 +385,7 @@ rules:
   document-start: disable
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // Should work exactly as before
     let yaml = "key: value   \n";
@@ -322,7 +406,7 @@ rules:
   document-start: disable
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     // line-length should use custom max: 100
     let yaml = format!("key: {}\n", "x".repeat(90));
@@ -348,7 +432,7 @@ rules:
     max: 120
 "#;
     let config = Config::load_from_str(config_yaml).unwrap();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     let yaml = format!("key: {}\n", "x".repeat(120));
     let problems = linter.lint_string(&yaml).unwrap();
@@ -430,3 +514,321 @@ rules:
         "Error message should indicate boolean type required"
     );
 }
+
+#[test]
+fn test_truthy_invalid_fix_style_error() {
+    let config_yaml = r#"
+rules:
+  truthy:
+    fix-style: rewrite
+"#;
+    let result = Config::load_from_str(config_yaml);
+    assert!(result.is_err(), "Expected error for invalid fix-style");
+    assert!(result.unwrap_err().to_string().contains("fix-style"));
+}
+
+#[test]
+fn test_extends_file_deep_merges_rule_options() {
+    let config = Config::load_from_file(&extends_fixture("team.yaml")).unwrap();
+
+    // line-length keeps the base level and takes the team's max
+    assert_eq!(
+        config.rules.get("line-length"),
+        Some(&RuleConfig::Detailed {
+            level: RuleLevel::Warning,
+            options: RuleOptions::LineLength { max: 120 },
+        })
+    );
+
+    // truthy keeps the base allowed values and takes the team's level
+    match config.rules.get("truthy") {
+        Some(RuleConfig::Detailed {
+            level,
+            options: RuleOptions::Truthy { allowed_values, .. },
+        }) => {
+            assert_eq!(*level, RuleLevel::Error);
+            assert_eq!(allowed_values, &["true", "false", "yes", "no"]);
+        }
+        other => panic!("Expected detailed truthy config, got {:?}", other),
+    }
+
+    // Rules from the default preset at the root of the chain are kept
+    assert_eq!(
+        config.get_rule_level("key-duplicates"),
+        Some(RuleLevel::Error)
+    );
+}
+
+#[test]
+fn test_extends_multiple_parents_later_wins() {
+    let config = Config::load_from_file(&extends_fixture("multiple.yaml")).unwrap();
+
+    assert_eq!(
+        config.get_rule_level("line-length"),
+        Some(RuleLevel::Warning)
+    );
+    assert_eq!(
+        config.get_rule_level("document-start"),
+        Some(RuleLevel::Error)
+    );
+    assert_eq!(
+        config.get_rule_level("trailing-spaces"),
+        Some(RuleLevel::Warning)
+    );
+}
+
+#[test]
+fn test_extends_cycle_is_rejected() {
+    let err = Config::load_from_file(&extends_fixture("cycle-a.yaml"))
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("Circular extends"), "{}", err);
+    assert!(err.contains("cycle-a.yaml -> "), "{}", err);
+    assert!(err.contains("cycle-b.yaml -> "), "{}", err);
+}
+
+#[test]
+fn test_extends_error_names_file_chain() {
+    let err = Config::load_from_file(&extends_fixture("extends-invalid.yaml"))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("`rules.trailing-spaces` must be error, warning or disable"),
+        "{}",
+        err
+    );
+    assert!(
+        err.contains("extends-invalid.yaml -> ") && err.ends_with("invalid-level.yaml)"),
+        "{}",
+        err
+    );
+
+    let err = Config::load_from_file(&extends_fixture("extends-missing.yaml"))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("does-not-exist.yaml"), "{}", err);
+    assert!(err.contains("extended from "), "{}", err);
+    assert!(err.contains("extends-missing.yaml"), "{}", err);
+}
+
+#[test]
+fn test_overrides_per_directory_rules() {
+    let config_yaml = r#"
+extends: default
+overrides:
+  - files: ["k8s/**"]
+    rules:
+      indentation:
+        spaces: 2
+      document-start:
+        present: true
+  - files: ["ansible/**"]
+    rules:
+      truthy:
+        allowed-values: ["true", "false", "yes", "no"]
+"#;
+    let linter = Linter::new(Config::load_from_str(config_yaml).unwrap()).unwrap();
+    let content = "enabled: yes\nitems:\n    - a\n";
+
+    let rules = |path: &str| -> Vec<String> {
+        linter
+            .lint_content(Path::new(path), content)
+            .unwrap()
+            .into_iter()
+            .map(|problem| problem.rule)
+            .collect()
+    };
+
+    assert_eq!(
+        rules("k8s/deploy.yaml"),
+        vec!["document-start", "truthy", "indentation"]
+    );
+    assert_eq!(rules("ansible/site.yaml"), Vec::<String>::new());
+    assert_eq!(rules("other.yaml"), vec!["truthy"]);
+}
+
+#[test]
+fn test_unknown_option_suggests_closest_key() {
+    let config_yaml = r#"
+rules:
+  colons:
+    max-spaces-afer: 2
+"#;
+    let err = Config::load_from_str(config_yaml).unwrap_err().to_string();
+    assert!(
+        err.contains(
+            "unknown key `rules.colons.max-spaces-afer`, did you mean `max-spaces-after`?"
+        ),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_wrong_option_type_is_rejected() {
+    let config_yaml = r#"
+rules:
+  line-length:
+    max: "120"
+"#;
+    let err = Config::load_from_str(config_yaml).unwrap_err().to_string();
+    assert!(
+        err.contains(
+            "`rules.line-length.max` must be a non-negative integer, found string \"120\""
+        ),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_unknown_rule_is_rejected() {
+    let config_yaml = r#"
+rules:
+  trailing-space: disable
+"#;
+    let err = Config::load_from_str(config_yaml).unwrap_err().to_string();
+    assert!(
+        err.contains("unknown rule `rules.trailing-space`, did you mean `trailing-spaces`?"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_unknown_key_in_override_names_path() {
+    let config_yaml = r#"
+overrides:
+  - files: ["k8s/**"]
+    rules:
+      indentation:
+        spacse: 2
+"#;
+    let err = Config::load_from_str(config_yaml).unwrap_err().to_string();
+    assert!(
+        err.contains("`overrides[0].rules.indentation.spacse`, did you mean `spaces`?"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_unknown_top_level_key_is_rejected() {
+    let err = Config::load_from_str("rule:\n  truthy: disable\n")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("unknown key `rule`, did you mean `rules`?"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_validation_error_names_file() {
+    let err = Config::load_from_file(&extends_fixture("invalid-level.yaml"))
+        .unwrap_err()
+        .to_string();
+    assert!(err.ends_with("invalid-level.yaml)"), "{}", err);
+}
+
+#[test]
+fn test_level_only_mapping_for_rule_without_options() {
+    let config_yaml = r#"
+rules:
+  trailing-spaces:
+    level: warning
+"#;
+    let config = Config::load_from_str(config_yaml).unwrap();
+    assert_eq!(
+        config.rules.get("trailing-spaces"),
+        Some(&RuleConfig::Level(RuleLevel::Warning))
+    );
+}
+
+#[test]
+fn test_create_registry_rejects_unknown_rule() {
+    let mut config = Config::new();
+    config
+        .rules
+        .insert("no-tabs".to_string(), RuleConfig::Level(RuleLevel::Error));
+
+    let err = config.create_registry().unwrap_err();
+    assert!(err.to_string().contains("no-tabs"), "{}", err);
+    assert!(Linter::new(config).is_err());
+}
+
+#[test]
+fn test_default_yaml_files() {
+    let config = Config::new();
+    assert!(config.is_yaml_file(Path::new("test.yaml")));
+    assert!(config.is_yaml_file(Path::new("dir/test.yml")));
+    assert!(config.is_yaml_file(Path::new(".yamllint")));
+    assert!(config.is_yaml_file(Path::new(".yamllint.yaml")));
+    assert!(!config.is_yaml_file(Path::new("test.txt")));
+    assert!(!config.is_yaml_file(Path::new("test")));
+}
+
+#[test]
+fn test_custom_yaml_files() {
+    let config_yaml = r#"
+yaml-files:
+  - "*.yaml"
+  - "*.yaml.j2"
+  - .clang-format
+  - charts/*/Chart.lock
+"#;
+    let config = Config::load_from_str(config_yaml).unwrap();
+    assert!(config.is_yaml_file(Path::new("app.yaml")));
+    assert!(config.is_yaml_file(Path::new("templates/app.yaml.j2")));
+    assert!(config.is_yaml_file(Path::new("src/.clang-format")));
+    assert!(config.is_yaml_file(Path::new("./charts/web/Chart.lock")));
+    assert!(!config.is_yaml_file(Path::new("Chart.lock")));
+    assert!(!config.is_yaml_file(Path::new("app.yml")));
+}
+
+#[test]
+fn test_invalid_yaml_files() {
+    let err = Config::load_from_str("yaml-files: 3\n")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("`yaml-files` must be a glob or a list of globs, found number 3"),
+        "{}",
+        err
+    );
+
+    let err = Config::load_from_str("yaml-files: ['[a']\n")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Invalid yaml-files glob '[a'"), "{}", err);
+}
+
+#[test]
+fn test_yamllint_ignore_from_file() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("tests/fixtures/config/yamllint");
+    // The ignore files are found next to the config, not in the current
+    // directory
+    let config = Config::load_from_file(&dir.join("config.yaml")).unwrap();
+
+    assert!(config.is_ignored(&dir.join("build/out.yaml")));
+    assert!(!config.is_ignored(&dir.join("src/build.yaml")));
+
+    let fixture = dir.join("tests/fixtures/dup.yaml");
+    assert!(!config.is_ignored(&fixture));
+    assert_eq!(
+        config.for_path(&fixture).get_rule_level("key-duplicates"),
+        Some(RuleLevel::Disable)
+    );
+    assert_eq!(
+        config
+            .for_path(&dir.join("app.yaml"))
+            .get_rule_level("key-duplicates"),
+        Some(RuleLevel::Error)
+    );
+    assert!(config.compat_notes.is_empty(), "{:?}", config.compat_notes);
+}
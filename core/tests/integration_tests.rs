@@ -1,7 +1,7 @@
 //! Integration tests for yaml-lint-rs
 
 use std::path::PathBuf;
-use yaml_lint_core::{Config, Fixer, Linter};
+use yaml_lint_core::{Config, ConfigResolver, Fixer, Linter};
 
 fn fixture_path(name: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -116,7 +116,7 @@ rules:
 "#;
 
     let config = Config::load_from_str(config_yaml).expect("Failed to load config");
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     let yaml = "key: value   \n";
     let problems = linter.lint_string(yaml).expect("Failed to lint string");
@@ -128,7 +128,7 @@ rules:
 #[test]
 fn test_relaxed_preset() {
     let config = Config::with_relaxed_preset();
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     let yaml = "key: value   \n";
     let problems = linter.lint_string(yaml).expect("Failed to lint string");
@@ -145,7 +145,7 @@ fn test_disable_rule() {
         "trailing-spaces".to_string(),
         yaml_lint_core::config::RuleConfig::Level(yaml_lint_core::rules::RuleLevel::Disable),
     );
-    let linter = Linter::new(config);
+    let linter = Linter::new(config).unwrap();
 
     let yaml = "key: value   \n";
     let problems = linter.lint_string(yaml).expect("Failed to lint string");
@@ -177,7 +177,7 @@ fn test_problems_sorted() {
 #[test]
 fn test_fix_trailing_spaces() {
     let config = Config::with_default_preset();
-    let registry = config.create_registry();
+    let registry = config.create_registry().unwrap();
     let fixer = Fixer::new(&registry);
 
     let content = "key: value   \nkey2: value2  \n";
@@ -194,7 +194,7 @@ fn test_fix_trailing_spaces() {
 #[test]
 fn test_fix_newline_at_end() {
     let config = Config::with_default_preset();
-    let registry = config.create_registry();
+    let registry = config.create_registry().unwrap();
     let fixer = Fixer::new(&registry);
 
     let content = "key: value";
@@ -208,7 +208,7 @@ fn test_fix_newline_at_end() {
 #[test]
 fn test_fix_empty_lines() {
     let config = Config::with_default_preset();
-    let registry = config.create_registry();
+    let registry = config.create_registry().unwrap();
     let fixer = Fixer::new(&registry);
 
     let content = "key1: value1\n\n\n\nkey2: value2\n";
@@ -226,7 +226,7 @@ fn test_fix_empty_lines() {
 #[test]
 fn test_fix_multiple_issues() {
     let config = Config::with_default_preset();
-    let registry = config.create_registry();
+    let registry = config.create_registry().unwrap();
     let fixer = Fixer::new(&registry);
 
     let content = "key: value   "; // trailing space + no newline
@@ -240,7 +240,7 @@ fn test_fix_multiple_issues() {
 #[test]
 fn test_fix_no_changes_needed() {
     let config = Config::with_default_preset();
-    let registry = config.create_registry();
+    let registry = config.create_registry().unwrap();
     let fixer = Fixer::new(&registry);
 
     let content = "key: value\n";
@@ -253,7 +253,7 @@ fn test_fix_no_changes_needed() {
 #[test]
 fn test_fix_preserves_valid_content() {
     let config = Config::with_default_preset();
-    let registry = config.create_registry();
+    let registry = config.create_registry().unwrap();
     let fixer = Fixer::new(&registry);
 
     let content = "name: test\nitems:\n  - one\n  - two\n";
@@ -266,7 +266,7 @@ fn test_fix_preserves_valid_content() {
 #[test]
 fn test_fix_unfixable_problems() {
     let config = Config::with_default_preset();
-    let registry = config.create_registry();
+    let registry = config.create_registry().unwrap();
     let fixer = Fixer::new(&registry);
 
     // Duplicate keys cannot be auto-fixed
@@ -285,7 +285,7 @@ fn test_fix_unfixable_problems() {
 #[test]
 fn test_fix_fixture_file() {
     let config = Config::with_default_preset();
-    let registry = config.create_registry();
+    let registry = config.create_registry().unwrap();
     let fixer = Fixer::new(&registry);
 
     let path = fixture_path("invalid/trailing-spaces.yaml");
@@ -305,3 +305,205 @@ fn test_fix_fixture_file() {
         }
     }
 }
+
+#[test]
+fn test_fix_indentation_complex_fixture() {
+    let config = Config::load_from_str("rules:\n  indentation:\n    spaces: 4\n").unwrap();
+    let registry = config.create_registry().unwrap();
+    let fixer = Fixer::new(&registry);
+
+    let path = fixture_path("valid/complex.yaml");
+    let content = std::fs::read_to_string(&path).expect("Failed to read fixture");
+    let result = fixer.fix(&path.display().to_string(), &content);
+
+    assert!(result.fixes_by_rule.contains_key("indentation"));
+    let fixed = result.fixed_content.expect("Expected fixed content");
+    assert!(fixed.contains("\n    database:\n        host: localhost\n"));
+    assert!(fixed.contains("\n        - name: Alice\n          email: alice@example.com\n"));
+
+    // The re-indented file passes the rule and fixes back to the original
+    let linter = Linter::new(config).unwrap();
+    assert!(linter.lint_string(&fixed).unwrap().is_empty());
+
+    let config = Config::load_from_str("rules:\n  indentation:\n    spaces: 2\n").unwrap();
+    let registry = config.create_registry().unwrap();
+    let result = Fixer::new(&registry).fix("complex.yaml", &fixed);
+    assert_eq!(result.fixed_content, Some(content));
+}
+
+#[test]
+fn test_fix_colons_fixture() {
+    let config = Config::with_default_preset();
+    let registry = config.create_registry().unwrap();
+    let fixer = Fixer::new(&registry);
+
+    let path = fixture_path("invalid/bad-colons.yaml");
+    let content = std::fs::read_to_string(&path).expect("Failed to read fixture");
+    let result = fixer.fix(&path.display().to_string(), &content);
+
+    assert!(result.fixes_by_rule.contains_key("colons"));
+    let fixed = result.fixed_content.expect("Expected fixed content");
+    assert!(fixed.contains("\nmultiple: issues\n"));
+    assert!(fixed.contains("\n  grandchild: also_bad\n"));
+
+    let linter = Linter::new(config).unwrap();
+    let problems = linter.lint_string(&fixed).unwrap();
+    assert!(
+        problems.iter().all(|p| p.rule != "colons"),
+        "Colon problems remain: {:?}",
+        problems
+    );
+}
+
+#[test]
+fn test_fix_truthy_canonical() {
+    let config = Config::load_from_str(
+        r#"
+extends: default
+rules:
+  truthy:
+    fix-style: canonical
+"#,
+    )
+    .unwrap();
+    let registry = config.create_registry().unwrap();
+    let fixer = Fixer::new(&registry);
+
+    let content = "debug: yes\nverbose: off\nname: value\n";
+    let result = fixer.fix("test.yaml", content);
+
+    assert_eq!(result.fixes_by_rule.get("truthy"), Some(&2));
+    assert_eq!(
+        result.fixed_content,
+        Some("debug: true\nverbose: false\nname: value\n".to_string())
+    );
+    assert!(!result.has_unfixable());
+}
+
+#[test]
+fn test_truthy_unfixable_without_fix_style() {
+    let config = Config::with_default_preset();
+    let registry = config.create_registry().unwrap();
+    let fixer = Fixer::new(&registry);
+
+    let result = fixer.fix("test.yaml", "debug: yes\n");
+
+    assert!(!result.has_fixes());
+    assert!(result.unfixable_problems.iter().any(|p| p.rule == "truthy"));
+}
+
+#[test]
+fn test_fix_document_start_multi_document() {
+    let config = Config::load_from_str(
+        r#"
+rules:
+  document-start:
+    present: true
+"#,
+    )
+    .unwrap();
+    let registry = config.create_registry().unwrap();
+    let fixer = Fixer::new(&registry);
+
+    let content = "# header\na: 1\n...\nb: 2\n";
+    let result = fixer.fix("test.yaml", content);
+
+    assert_eq!(result.fixes_by_rule.get("document-start"), Some(&2));
+    assert_eq!(
+        result.fixed_content,
+        Some("# header\n---\na: 1\n...\n---\nb: 2\n".to_string())
+    );
+}
+
+#[test]
+fn test_problem_ranges_on_invalid_fixtures() {
+    let config = Config::load_from_str(
+        r#"
+extends: default
+rules:
+  document-start:
+    present: true
+  truthy:
+    check-keys: true
+"#,
+    )
+    .unwrap();
+    let linter = Linter::new(config).unwrap();
+
+    for name in [
+        "bad-colons.yaml",
+        "bad-indentation.yaml",
+        "duplicate-keys.yaml",
+        "long-lines.yaml",
+        "multiple-issues.yaml",
+        "tabs-indentation.yaml",
+        "trailing-spaces.yaml",
+    ] {
+        let path = fixture_path(&format!("invalid/{}", name));
+        let content = std::fs::read_to_string(&path).unwrap();
+        let problems = linter.lint_string(&content).unwrap();
+        assert!(!problems.is_empty(), "Expected problems in {}", name);
+
+        for problem in &problems {
+            let (Some(end_line), Some(end_column), Some(span)) =
+                (problem.end_line, problem.end_column, problem.span.clone())
+            else {
+                panic!("Missing range for {:?} in {}", problem, name);
+            };
+            assert!(
+                (end_line, end_column) >= (problem.line, problem.column),
+                "Range ends before it starts: {:?} in {}",
+                problem,
+                name
+            );
+            assert!(
+                span.start <= span.end && span.end <= content.len(),
+                "Span out of bounds: {:?} in {}",
+                problem,
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_config_discovery_per_directory() {
+    let mut resolver = ConfigResolver::new(Config::with_relaxed_preset()).unwrap();
+
+    let mut rules = |dir: &str| -> Vec<String> {
+        let path = fixture_path(&format!("config/discovery/{}/sample.yaml", dir));
+        let linter = resolver
+            .linter_for(&path)
+            .expect("Failed to resolve config");
+        let problems = linter.lint_file(&path).expect("Failed to lint file");
+        problems.into_iter().map(|problem| problem.rule).collect()
+    };
+
+    // No config of its own: uses config/discovery/.yamllint
+    assert_eq!(
+        rules("plain"),
+        vec!["line-length", "trailing-spaces", "truthy"]
+    );
+    // Layered on top of config/discovery/.yamllint, whose override matches
+    assert_eq!(rules("project"), vec!["trailing-spaces"]);
+    // `root: true` ignores the parent configs
+    assert_eq!(rules("project/isolated"), vec!["truthy"]);
+}
+
+#[test]
+fn test_config_discovery_caches_per_directory() {
+    let mut resolver = ConfigResolver::new(Config::default()).unwrap();
+
+    let plain = resolver
+        .linter_for(&fixture_path("config/discovery/plain/sample.yaml"))
+        .unwrap();
+    let parent = resolver
+        .linter_for(&fixture_path("config/discovery/.yamllint"))
+        .unwrap();
+    let project = resolver
+        .linter_for(&fixture_path("config/discovery/project/sample.yaml"))
+        .unwrap();
+
+    assert!(std::sync::Arc::ptr_eq(&plain, &parent));
+    assert!(!std::sync::Arc::ptr_eq(&plain, &project));
+}